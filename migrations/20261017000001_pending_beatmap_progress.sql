@@ -0,0 +1,8 @@
+-- Suivi de la progression d'un import, renseigné par le worker de traitement
+ALTER TABLE pending_beatmap
+    ADD COLUMN IF NOT EXISTS processing_started_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS processed_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS failed_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS failure_reason TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_pending_beatmap_osu_hash ON pending_beatmap (osu_hash);
@@ -0,0 +1,22 @@
+-- Sélections hebdomadaires : semaine du lundi 00:00 UTC au lundi suivant
+CREATE TABLE IF NOT EXISTS weekly (
+    id SERIAL PRIMARY KEY,
+    starts_at TIMESTAMP NOT NULL UNIQUE,
+    ends_at TIMESTAMP NOT NULL,
+    -- Graine du tirage ; avec les mêmes filtres et le même pool, même sélection
+    seed BIGINT NOT NULL,
+    -- Query string des filtres utilisés, telle que reçue par l'API
+    filters TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CHECK (ends_at > starts_at)
+);
+
+CREATE TABLE IF NOT EXISTS weekly_beatmap (
+    weekly_id INTEGER NOT NULL REFERENCES weekly (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    beatmap_id INTEGER NOT NULL REFERENCES beatmap (id) ON DELETE CASCADE,
+    centirate INTEGER NOT NULL,
+    PRIMARY KEY (weekly_id, position),
+    UNIQUE (weekly_id, beatmap_id)
+);
+
@@ -0,0 +1,19 @@
+-- Scores joués sur une difficulté à un rate stocké
+CREATE TABLE IF NOT EXISTS score (
+    id SERIAL PRIMARY KEY,
+    rates_id INTEGER NOT NULL REFERENCES rates (id) ON DELETE CASCADE,
+    player TEXT NOT NULL,
+    accuracy DOUBLE PRECISION NOT NULL CHECK (accuracy >= 0 AND accuracy <= 100),
+    count_perfect INTEGER NOT NULL CHECK (count_perfect >= 0),
+    count_great INTEGER NOT NULL CHECK (count_great >= 0),
+    count_good INTEGER NOT NULL CHECK (count_good >= 0),
+    count_ok INTEGER NOT NULL CHECK (count_ok >= 0),
+    count_meh INTEGER NOT NULL CHECK (count_meh >= 0),
+    count_miss INTEGER NOT NULL CHECK (count_miss >= 0),
+    max_combo INTEGER NOT NULL CHECK (max_combo >= 0),
+    played_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_score_rates ON score (rates_id, accuracy DESC);
+CREATE INDEX IF NOT EXISTS idx_score_player ON score (player, played_at DESC);
@@ -0,0 +1,12 @@
+-- Clés d'API : seul le SHA-256 de la clé est stocké
+CREATE TABLE IF NOT EXISTS api_key (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    -- Début de la clé, pour la reconnaître sans l'exposer
+    prefix TEXT NOT NULL,
+    -- Parmi `read`, `import`, `admin`
+    scopes TEXT[] NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMP
+);
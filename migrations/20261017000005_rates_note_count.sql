@@ -0,0 +1,23 @@
+-- Nombre de notes (longues notes comprises) d'une difficulté, recopié sur
+-- chacun de ses rates ; renseigné par le worker de traitement à l'import.
+-- NULL tant qu'il n'est pas connu : la soumission de scores ne le vérifie pas.
+ALTER TABLE rates
+    ADD COLUMN IF NOT EXISTS note_count INTEGER CHECK (note_count > 0);
+
+-- Rates déjà importés : total de jugements le plus fréquent parmi les scores
+-- de la difficulté, tous rates confondus
+UPDATE rates r
+SET note_count = t.total
+FROM (
+    SELECT DISTINCT ON (rt.beatmap_id)
+           rt.beatmap_id,
+           s.count_perfect + s.count_great + s.count_good
+               + s.count_ok + s.count_meh + s.count_miss AS total
+    FROM score s
+    JOIN rates rt ON rt.id = s.rates_id
+    GROUP BY rt.beatmap_id, total
+    ORDER BY rt.beatmap_id, COUNT(*) DESC, total DESC
+) t
+WHERE r.beatmap_id = t.beatmap_id
+  AND r.note_count IS NULL
+  AND t.total > 0;
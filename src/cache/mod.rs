@@ -0,0 +1,184 @@
+//! # Cache Module
+//!
+//! Cache mémoire des réponses JSON de lecture (beatmapsets, rates), indexé
+//! par route normalisée + query triée. Initialisé une fois au démarrage.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use axum::body::Bytes;
+use axum::http::HeaderMap;
+use moka::future::Cache;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::config::CacheConfig;
+
+static RESPONSE_CACHE: OnceCell<ResponseCache> = OnceCell::new();
+
+/// Réponse 200 telle qu'envoyée au client : corps et headers (dont `ETag`, `Cache-Control`)
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub body: Bytes,
+    pub headers: HeaderMap,
+}
+
+pub struct ResponseCache {
+    entries: Cache<String, CachedResponse>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_ratio: f64,
+    pub entries: u64,
+}
+
+/// Initialise le cache global ; sans appel, le middleware laisse tout passer
+pub fn init(config: &CacheConfig) {
+    let entries = Cache::builder()
+        .max_capacity(config.max_capacity)
+        .time_to_live(Duration::from_secs(config.ttl_seconds))
+        .support_invalidation_closures()
+        .build();
+
+    let _ = RESPONSE_CACHE.set(ResponseCache {
+        entries,
+        hits: AtomicU64::new(0),
+        misses: AtomicU64::new(0),
+    });
+}
+
+pub fn get() -> Option<&'static ResponseCache> {
+    RESPONSE_CACHE.get()
+}
+
+/// Clé de cache : chemin + paramètres décodés et triés, pour que
+/// `?a=1&b=2` et `?b=2&a=1` partagent la même entrée.
+pub fn normalize_key(path: &str, query: Option<&str>) -> String {
+    let mut pairs: Vec<(String, String)> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(k), decode(v))
+        })
+        .collect();
+    pairs.sort();
+
+    let path = path.trim_end_matches('/');
+    if pairs.is_empty() {
+        return path.to_string();
+    }
+    let query = pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}?{}", path, query)
+}
+
+fn decode(raw: &str) -> String {
+    let raw = raw.replace('+', " ");
+    urlencoding::decode(&raw)
+        .map(|s| s.into_owned())
+        .unwrap_or(raw)
+}
+
+impl ResponseCache {
+    pub async fn lookup(&self, key: &str) -> Option<CachedResponse> {
+        let found = self.entries.get(key).await;
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    pub async fn store(&self, key: String, response: CachedResponse) {
+        self.entries.insert(key, response).await;
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let total = hits + misses;
+        CacheStats {
+            hits,
+            misses,
+            hit_ratio: if total == 0 {
+                0.0
+            } else {
+                hits as f64 / total as f64
+            },
+            entries: self.entries.entry_count(),
+        }
+    }
+
+    /// Supprime tout ce qui dépend d'un beatmapset : sa fiche, celles de
+    /// ses difficultés et leurs rates, et toutes les listes (dont le contenu peut changer).
+    pub fn invalidate_beatmapset(&self, beatmapset_osu_id: i32, beatmap_osu_ids: &[i32]) {
+        let set_path = format!("/api/beatmapsets/{}", beatmapset_osu_id);
+        let beatmap_paths: Vec<String> = beatmap_osu_ids
+            .iter()
+            .map(|id| format!("/api/beatmaps/{}", id))
+            .collect();
+
+        let result = self.entries.invalidate_entries_if(move |key, _| {
+            let path = key.split('?').next().unwrap_or(key);
+            path == set_path
+                || path == "/api/beatmapsets"
+                || beatmap_paths.iter().any(|p| {
+                    path.strip_prefix(p.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+        });
+        if let Err(err) = result {
+            tracing::error!(error = %err, "failed to register cache invalidation");
+        }
+    }
+
+    pub fn invalidate_all(&self) {
+        self.entries.invalidate_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_order_does_not_matter() {
+        assert_eq!(
+            normalize_key("/api/beatmapsets", Some("b=2&a=1")),
+            normalize_key("/api/beatmapsets", Some("a=1&b=2"))
+        );
+    }
+
+    #[test]
+    fn values_are_decoded() {
+        assert_eq!(
+            normalize_key("/api/beatmapsets", Some("search=foo+bar&artist=a%20b")),
+            "/api/beatmapsets?artist=a b&search=foo bar"
+        );
+    }
+
+    #[test]
+    fn trailing_slash_and_empty_query_are_ignored() {
+        assert_eq!(normalize_key("/api/rates/", None), "/api/rates");
+        assert_eq!(normalize_key("/api/rates", Some("")), "/api/rates");
+        assert_eq!(normalize_key("/api/rates", Some("&&")), "/api/rates");
+    }
+
+    #[test]
+    fn key_without_value_is_kept() {
+        assert_eq!(normalize_key("/api/x", Some("flag")), "/api/x?flag=");
+    }
+}
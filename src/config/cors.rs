@@ -0,0 +1,234 @@
+//! # CORS
+//!
+//! Construit le `CorsLayer` à partir de `CorsConfig`. Toute entrée invalide
+//! (origine, méthode ou header) fait échouer le démarrage avec un message explicite.
+
+use std::fmt;
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+use super::types::CorsConfig;
+
+#[derive(Debug)]
+pub enum CorsConfigError {
+    InvalidOrigin(String, &'static str),
+    InvalidMethod(String),
+    InvalidHeader(String),
+    WildcardWithCredentials(&'static str),
+    /// Variable d'environnement et valeur non interprétable
+    InvalidValue(&'static str, String),
+}
+
+impl fmt::Display for CorsConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOrigin(origin, reason) => {
+                write!(f, "invalid CORS origin `{}`: {}", origin, reason)
+            }
+            Self::InvalidMethod(method) => write!(f, "invalid CORS method `{}`", method),
+            Self::InvalidHeader(header) => write!(f, "invalid CORS header `{}`", header),
+            Self::WildcardWithCredentials(var) => write!(
+                f,
+                "`*` in {} cannot be combined with CORS_ALLOW_CREDENTIALS=true",
+                var
+            ),
+            Self::InvalidValue(var, value) => write!(f, "invalid {} `{}`", var, value),
+        }
+    }
+}
+
+impl std::error::Error for CorsConfigError {}
+
+/// Origine autorisée : soit exacte, soit un motif `scheme://*.domaine[:port]`
+#[derive(Debug, Clone)]
+enum OriginPattern {
+    Exact(HeaderValue),
+    Subdomain { scheme: String, suffix: String },
+}
+
+impl OriginPattern {
+    fn parse(raw: &str) -> Result<Self, CorsConfigError> {
+        let invalid = |reason| CorsConfigError::InvalidOrigin(raw.to_string(), reason);
+
+        let (scheme, host) = raw
+            .split_once("://")
+            .ok_or_else(|| invalid("expected `scheme://host[:port]`"))?;
+        if scheme != "http" && scheme != "https" {
+            return Err(invalid("scheme must be http or https"));
+        }
+        if host.is_empty() || host.contains('/') {
+            return Err(invalid("origin must not contain a path"));
+        }
+
+        match host.strip_prefix("*.") {
+            Some(suffix) => {
+                if suffix.is_empty() || suffix.contains('*') {
+                    return Err(invalid("wildcard must be followed by a domain"));
+                }
+                Ok(Self::Subdomain {
+                    scheme: scheme.to_string(),
+                    suffix: format!(".{}", suffix.to_ascii_lowercase()),
+                })
+            }
+            None if host.contains('*') => Err(invalid("wildcard is only allowed as `*.domain`")),
+            None => HeaderValue::from_str(raw)
+                .map(Self::Exact)
+                .map_err(|_| invalid("not a valid header value")),
+        }
+    }
+
+    fn matches(&self, origin: &HeaderValue) -> bool {
+        match self {
+            Self::Exact(expected) => expected == origin,
+            Self::Subdomain { scheme, suffix } => {
+                let Ok(origin) = origin.to_str() else {
+                    return false;
+                };
+                let Some((origin_scheme, host)) = origin.split_once("://") else {
+                    return false;
+                };
+                let host = host.to_ascii_lowercase();
+                origin_scheme == scheme
+                    && host.len() > suffix.len()
+                    && host.ends_with(suffix.as_str())
+                    && !host.contains('/')
+            }
+        }
+    }
+}
+
+fn parse_headers(values: &[String]) -> Result<Vec<HeaderName>, CorsConfigError> {
+    values
+        .iter()
+        .map(|h| {
+            HeaderName::from_bytes(h.as_bytes())
+                .map_err(|_| CorsConfigError::InvalidHeader(h.clone()))
+        })
+        .collect()
+}
+
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|v| v == "*")
+}
+
+impl CorsConfig {
+    /// Construit le layer CORS, ou retourne l'entrée fautive
+    pub fn layer(&self) -> Result<CorsLayer, CorsConfigError> {
+        let allow_origin = if is_wildcard(&self.allowed_origins) {
+            if self.allow_credentials {
+                return Err(CorsConfigError::WildcardWithCredentials(
+                    "CORS_ALLOWED_ORIGINS",
+                ));
+            }
+            AllowOrigin::any()
+        } else {
+            let patterns = self
+                .allowed_origins
+                .iter()
+                .map(|o| OriginPattern::parse(o))
+                .collect::<Result<Vec<_>, _>>()?;
+            AllowOrigin::predicate(move |origin, _| patterns.iter().any(|p| p.matches(origin)))
+        };
+
+        let allow_methods = if is_wildcard(&self.allowed_methods) {
+            if self.allow_credentials {
+                return Err(CorsConfigError::WildcardWithCredentials(
+                    "CORS_ALLOWED_METHODS",
+                ));
+            }
+            AllowMethods::any()
+        } else {
+            let methods = self
+                .allowed_methods
+                .iter()
+                .map(|m| {
+                    Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                        .map_err(|_| CorsConfigError::InvalidMethod(m.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            AllowMethods::list(methods)
+        };
+
+        let allow_headers = if is_wildcard(&self.allowed_headers) {
+            if self.allow_credentials {
+                return Err(CorsConfigError::WildcardWithCredentials(
+                    "CORS_ALLOWED_HEADERS",
+                ));
+            }
+            AllowHeaders::any()
+        } else {
+            AllowHeaders::list(parse_headers(&self.allowed_headers)?)
+        };
+
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            .allow_headers(allow_headers)
+            .expose_headers(parse_headers(&self.exposed_headers)?)
+            .allow_credentials(self.allow_credentials)
+            .max_age(Duration::from_secs(self.max_age)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(raw: &'static str) -> HeaderValue {
+        HeaderValue::from_static(raw)
+    }
+
+    #[test]
+    fn exact_origin_matches_only_itself() {
+        let pattern = OriginPattern::parse("https://app.example.com").unwrap();
+        assert!(pattern.matches(&origin("https://app.example.com")));
+        assert!(!pattern.matches(&origin("http://app.example.com")));
+        assert!(!pattern.matches(&origin("https://other.example.com")));
+    }
+
+    #[test]
+    fn subdomain_pattern_requires_a_subdomain_and_same_scheme() {
+        let pattern = OriginPattern::parse("https://*.Example.com").unwrap();
+        assert!(pattern.matches(&origin("https://app.example.com")));
+        assert!(pattern.matches(&origin("https://a.b.EXAMPLE.com")));
+        assert!(!pattern.matches(&origin("https://example.com")));
+        assert!(!pattern.matches(&origin("http://app.example.com")));
+        assert!(!pattern.matches(&origin("https://app.example.com.evil.io")));
+        assert!(!pattern.matches(&origin("https://evilexample.com")));
+    }
+
+    #[test]
+    fn malformed_origins_are_rejected() {
+        for raw in [
+            "app.example.com",
+            "ftp://app.example.com",
+            "https://",
+            "https://app.example.com/path",
+            "https://*.",
+            "https://*.*.example.com",
+            "https://app.*.example.com",
+        ] {
+            assert!(
+                OriginPattern::parse(raw).is_err(),
+                "{raw} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn wildcard_cannot_be_combined_with_credentials() {
+        let config = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allow_credentials: true,
+            ..CorsConfig::default()
+        };
+        assert!(matches!(
+            config.layer(),
+            Err(CorsConfigError::WildcardWithCredentials(
+                "CORS_ALLOWED_ORIGINS"
+            ))
+        ));
+    }
+}
@@ -14,7 +14,7 @@ impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             level: "info".to_string(),
-            format: "json".to_string(),
+            format: LogFormat::Json,
         }
     }
 }
@@ -34,6 +34,32 @@ impl Default for CorsConfig {
                 "OPTIONS".to_string(),
             ],
             allowed_headers: vec!["content-type".to_string(), "authorization".to_string()],
+            exposed_headers: vec![],
+            allow_credentials: false,
+            max_age: 3600,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: 60,
+            max_capacity: 10_000,
+        }
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            read_per_minute: 300,
+            write_per_minute: 60,
+            strict_per_minute: 10,
+            auth_per_minute: 600,
+            max_clients: 100_000,
+            trusted_proxies: Vec::new(),
         }
     }
 }
@@ -48,6 +74,9 @@ impl Default for Config {
             database: DatabaseConfig::default(),
             logging: LoggingConfig::default(),
             cors: CorsConfig::default(),
+            cache: CacheConfig::default(),
+            auth: AuthConfig::default(),
+            rate_limit: RateLimitConfig::default(),
         }
     }
 }
@@ -1,8 +1,10 @@
 use db::config::DatabaseConfig;
 use dotenvy::var;
 use tracing::info;
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
+use super::cors::CorsConfigError;
+use super::log_format::{Logfmt, UnknownLogFormat};
 use super::types::*;
 
 impl ServerConfig {
@@ -18,17 +20,20 @@ impl ServerConfig {
 }
 
 impl LoggingConfig {
-    pub fn load() -> Self {
-        LoggingConfig {
+    pub fn load() -> Result<Self, UnknownLogFormat> {
+        Ok(LoggingConfig {
             level: var("LOG_LEVEL").unwrap_or_else(|_| Self::default().level),
-            format: var("LOG_FORMAT").unwrap_or_else(|_| Self::default().format),
-        }
+            format: match var("LOG_FORMAT") {
+                Ok(format) => format.parse()?,
+                Err(_) => Self::default().format,
+            },
+        })
     }
 }
 
 impl CorsConfig {
-    pub fn load() -> Self {
-        CorsConfig {
+    pub fn load() -> Result<Self, CorsConfigError> {
+        Ok(CorsConfig {
             allowed_origins: var("CORS_ALLOWED_ORIGINS")
                 .unwrap_or_else(|_| Self::default().allowed_origins.join(","))
                 .split(',')
@@ -44,23 +49,128 @@ impl CorsConfig {
                 .split(',')
                 .map(|s| s.trim().to_string())
                 .collect(),
+            exposed_headers: var("CORS_EXPOSED_HEADERS")
+                .unwrap_or_else(|_| Self::default().exposed_headers.join(","))
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            allow_credentials: match var("CORS_ALLOW_CREDENTIALS") {
+                Ok(v) => v
+                    .trim()
+                    .parse()
+                    .map_err(|_| CorsConfigError::InvalidValue("CORS_ALLOW_CREDENTIALS", v))?,
+                Err(_) => Self::default().allow_credentials,
+            },
+            max_age: match var("CORS_MAX_AGE") {
+                Ok(v) => v
+                    .trim()
+                    .parse()
+                    .map_err(|_| CorsConfigError::InvalidValue("CORS_MAX_AGE", v))?,
+                Err(_) => Self::default().max_age,
+            },
+        })
+    }
+}
+
+impl CacheConfig {
+    pub fn load() -> Self {
+        CacheConfig {
+            ttl_seconds: var("CACHE_TTL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::default().ttl_seconds),
+            max_capacity: var("CACHE_MAX_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::default().max_capacity),
         }
     }
 }
 
+impl AuthConfig {
+    pub fn load() -> Self {
+        AuthConfig {
+            require_read_key: var("AUTH_REQUIRE_READ_KEY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::default().require_read_key),
+            bootstrap_key: var("AUTH_BOOTSTRAP_KEY")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn load() -> Result<Self, InvalidTrustedProxy> {
+        // Une limite à 0 bloquerait toutes les requêtes : on garde la valeur par défaut
+        let per_minute = |name: &str, default: u32| {
+            var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        Ok(RateLimitConfig {
+            enabled: var("RATE_LIMIT_ENABLED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::default().enabled),
+            read_per_minute: per_minute(
+                "RATE_LIMIT_READ_PER_MINUTE",
+                Self::default().read_per_minute,
+            ),
+            write_per_minute: per_minute(
+                "RATE_LIMIT_WRITE_PER_MINUTE",
+                Self::default().write_per_minute,
+            ),
+            strict_per_minute: per_minute(
+                "RATE_LIMIT_STRICT_PER_MINUTE",
+                Self::default().strict_per_minute,
+            ),
+            auth_per_minute: per_minute(
+                "RATE_LIMIT_AUTH_PER_MINUTE",
+                Self::default().auth_per_minute,
+            ),
+            max_clients: var("RATE_LIMIT_MAX_CLIENTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::default().max_clients),
+            trusted_proxies: var("RATE_LIMIT_TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse().map_err(|_| InvalidTrustedProxy(s.to_string())))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 impl Config {
     /// Initialise le système de logging
-    fn init_logging(level: &str, _format: &str) {
+    fn init_logging(level: &str, format: LogFormat) {
         let env_filter = EnvFilter::try_from_default_env()
             .or_else(|_| EnvFilter::try_new(level))
             .unwrap_or_else(|_| EnvFilter::new("info"));
 
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(tracing_subscriber::fmt::layer())
-            .init();
+        let registry = tracing_subscriber::registry().with(env_filter);
+        match format {
+            // flatten_event: les champs structurés deviennent des clés JSON de premier niveau
+            LogFormat::Json => registry
+                .with(fmt::layer().json().flatten_event(true))
+                .init(),
+            LogFormat::Pretty => registry.with(fmt::layer().pretty()).init(),
+            LogFormat::Compact => registry.with(fmt::layer().compact()).init(),
+            LogFormat::Logfmt => registry.with(fmt::layer().event_format(Logfmt)).init(),
+        }
 
-        info!("Logging initialized with level: {}", level);
+        info!(
+            "Logging initialized with level: {} and format: {:?}",
+            level, format
+        );
     }
 
     /// Charge toute la config
@@ -70,11 +180,14 @@ impl Config {
         let config = Config {
             server: ServerConfig::load(),
             database: DatabaseConfig::load(),
-            logging: LoggingConfig::load(),
-            cors: CorsConfig::load(),
+            logging: LoggingConfig::load()?,
+            cors: CorsConfig::load()?,
+            cache: CacheConfig::load(),
+            auth: AuthConfig::load(),
+            rate_limit: RateLimitConfig::load()?,
         };
 
-        Self::init_logging(&config.logging.level, &config.logging.format);
+        Self::init_logging(&config.logging.level, config.logging.format);
 
         info!(
             "Configuration loaded successfully. Server will bind to: {}",
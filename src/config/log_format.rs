@@ -0,0 +1,109 @@
+//! # Log Format
+//!
+//! Parsing de `LOG_FORMAT` et formateur `logfmt` pour `tracing_subscriber`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::types::LogFormat;
+
+#[derive(Debug)]
+pub struct UnknownLogFormat(pub String);
+
+impl fmt::Display for UnknownLogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown LOG_FORMAT `{}` (expected one of: json, pretty, compact, logfmt)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownLogFormat {}
+
+impl FromStr for LogFormat {
+    type Err = UnknownLogFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "pretty" => Ok(Self::Pretty),
+            "compact" => Ok(Self::Compact),
+            "logfmt" => Ok(Self::Logfmt),
+            _ => Err(UnknownLogFormat(s.to_string())),
+        }
+    }
+}
+
+/// Formateur `logfmt` : `ts=… level=… target=… msg="…" key=value`
+pub struct Logfmt;
+
+impl<S, N> FormatEvent<S, N> for Logfmt
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        write!(
+            writer,
+            "ts={} level={} target={}",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            meta.level().as_str().to_ascii_lowercase(),
+            meta.target()
+        )?;
+
+        let mut visitor = LogfmtVisitor {
+            writer: &mut writer,
+            result: Ok(()),
+        };
+        event.record(&mut visitor);
+        visitor.result?;
+
+        writeln!(writer)
+    }
+}
+
+struct LogfmtVisitor<'a, 'w> {
+    writer: &'a mut Writer<'w>,
+    result: fmt::Result,
+}
+
+impl LogfmtVisitor<'_, '_> {
+    fn write_pair(&mut self, field: &Field, value: &str) {
+        if self.result.is_err() {
+            return;
+        }
+        let key = match field.name() {
+            "message" => "msg",
+            name => name,
+        };
+        self.result = if value.is_empty() || value.contains([' ', '"', '=']) {
+            write!(self.writer, " {}={:?}", key, value)
+        } else {
+            write!(self.writer, " {}={}", key, value)
+        };
+    }
+}
+
+impl Visit for LogfmtVisitor<'_, '_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.write_pair(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.write_pair(field, &format!("{:?}", value));
+    }
+}
@@ -1,5 +1,7 @@
+pub mod cors;
 pub mod default;
 pub mod load;
+pub mod log_format;
 pub mod types;
 
 pub use types::*;
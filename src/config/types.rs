@@ -1,3 +1,6 @@
+use std::fmt;
+use std::net::IpAddr;
+
 use db::config::DatabaseConfig;
 
 #[derive(Debug, Clone)]
@@ -6,24 +9,107 @@ pub struct ServerConfig {
     pub port: u16,
 }
 
+/// Format de sortie des logs, sélectionné par `LOG_FORMAT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Un objet JSON par ligne, champs structurés au premier niveau
+    Json,
+    Pretty,
+    Compact,
+    /// `key=value` sur une ligne
+    Logfmt,
+}
+
 #[derive(Debug, Clone)]
 pub struct LoggingConfig {
     pub level: String,
-    pub format: String,
+    pub format: LogFormat,
 }
 
 #[derive(Debug, Clone)]
 pub struct CorsConfig {
+    /// Origines exactes (`https://app.example.com`) ou motifs de sous-domaine
+    /// (`https://*.example.com`). `*` autorise toutes les origines.
     pub allowed_origins: Vec<String>,
     pub allowed_methods: Vec<String>,
     pub allowed_headers: Vec<String>,
+    pub exposed_headers: Vec<String>,
+    pub allow_credentials: bool,
+    /// Durée de mise en cache des requêtes preflight, en secondes
+    pub max_age: u64,
 }
 
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Durée de vie d'une entrée, en secondes
+    pub ttl_seconds: u64,
+    /// Nombre maximum de réponses gardées en mémoire
+    pub max_capacity: u64,
+}
+
+/// Sans configuration : lecture anonyme, aucune clé initiale
+#[derive(Clone, Default)]
+pub struct AuthConfig {
+    /// Exige une clé donnant `read` (`read`, `import` ou `admin`) pour toutes les routes `/api`
+    pub require_read_key: bool,
+    /// Clé admin enregistrée au démarrage, pour créer les premières clés
+    pub bootstrap_key: Option<String>,
+}
+
+impl std::fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field("require_read_key", &self.require_read_key)
+            .field(
+                "bootstrap_key",
+                &self.bootstrap_key.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+/// Limites par client (clé d'API, sinon IP), en requêtes par minute ;
+/// chaque limite sert aussi de rafale maximale
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Lectures (`GET`, `HEAD`)
+    pub read_per_minute: u32,
+    /// Autres méthodes
+    pub write_per_minute: u32,
+    /// Routes coûteuses : imports, tirage aléatoire, similaires, niveau et recommandations
+    pub strict_per_minute: u32,
+    /// Requêtes avec un header `Authorization`, par IP, avant la vérification de la clé
+    pub auth_per_minute: u32,
+    /// Nombre maximum de compteurs gardés en mémoire
+    pub max_clients: u64,
+    /// Proxies dont on accepte `X-Forwarded-For` ; sans eux, l'IP est celle de la connexion
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// Adresse invalide dans `RATE_LIMIT_TRUSTED_PROXIES`
+#[derive(Debug)]
+pub struct InvalidTrustedProxy(pub String);
+
+impl fmt::Display for InvalidTrustedProxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid IP address `{}` in RATE_LIMIT_TRUSTED_PROXIES",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidTrustedProxy {}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub server: ServerConfig,
     pub database: DatabaseConfig,
     pub logging: LoggingConfig,
-    #[allow(dead_code)]
     pub cors: CorsConfig,
+    pub cache: CacheConfig,
+    pub auth: AuthConfig,
+    pub rate_limit: RateLimitConfig,
 }
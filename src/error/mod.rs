@@ -0,0 +1,176 @@
+//! # Error Module
+//!
+//! Erreur unique renvoyée par les handlers. Chaque variante est rendue dans
+//! l'enveloppe `ApiResponse` avec le vrai code HTTP, un code d'erreur stable
+//! et l'identifiant de la requête.
+
+pub mod extract;
+
+use axum::{
+    Json,
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use dto::common::ApiResponse;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::middleware::request_id::current_request_id;
+
+/// Champ invalide dans une requête
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Contenu de `data` pour toute réponse d'erreur
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    /// Code machine, stable entre versions (`not_found`, `validation_failed`, ...)
+    #[schema(example = "not_found")]
+    pub code: &'static str,
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound(String),
+    Validation {
+        message: String,
+        fields: Vec<FieldError>,
+    },
+    /// Clé d'API absente ou invalide
+    Unauthorized(String),
+    /// Clé valide mais sans le scope requis
+    Forbidden(String),
+    Conflict(String),
+    RateLimited {
+        retry_after: u64,
+    },
+    /// Le détail est loggé par le handler, jamais exposé au client
+    Internal,
+}
+
+impl ApiError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation {
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn invalid_fields(fields: Vec<FieldError>) -> Self {
+        Self::Validation {
+            message: "Invalid request parameters".to_string(),
+            fields,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Validation { .. } => StatusCode::BAD_REQUEST,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not_found",
+            Self::Validation { .. } => "validation_failed",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::Forbidden(_) => "forbidden",
+            Self::Conflict(_) => "conflict",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::Internal => "internal_error",
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let code = self.code();
+
+        let (message, fields, retry_after) = match self {
+            Self::NotFound(message)
+            | Self::Unauthorized(message)
+            | Self::Forbidden(message)
+            | Self::Conflict(message) => (message, Vec::new(), None),
+            Self::Validation { message, fields } => (message, fields, None),
+            Self::RateLimited { retry_after } => (
+                "Too many requests".to_string(),
+                Vec::new(),
+                Some(retry_after),
+            ),
+            Self::Internal => ("Internal server error".to_string(), Vec::new(), None),
+        };
+
+        let body = ApiResponse {
+            message,
+            status: status.as_u16().to_string(),
+            data: Some(ErrorBody {
+                code,
+                request_id: current_request_id(),
+                fields,
+            }),
+        };
+
+        let mut response = (status, Json(body)).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::validation(rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::validation(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::validation(rejection.body_text())
+    }
+}
+
+/// Fallback pour les routes inconnues
+pub async fn route_not_found() -> ApiError {
+    ApiError::not_found("Route not found")
+}
@@ -0,0 +1,38 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::extract::Path;
+use crate::error::{ApiError, ErrorBody};
+use crate::models::api_key::query::revoke_api_key;
+use crate::models::api_key::types::ApiKeyDto;
+
+/// DELETE /api/keys/{id}
+#[utoipa::path(
+    delete,
+    path = "/api/keys/{id}",
+    params(("id" = i32, Path, description = "API key ID", example = 3)),
+    responses(
+        (status = 200, description = "Revoked key; revoking twice keeps the first revocation date", body = ApiResponse<ApiKeyDto>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `admin` scope", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "API key not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Auth"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<ApiKeyDto>>, ApiError> {
+    let key = revoke_api_key(db.get_pool(), id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to revoke API key {}", id);
+            ApiError::Internal
+        })?
+        .ok_or_else(|| ApiError::not_found(format!("API key {} not found", id)))?;
+
+    Ok(Json(ApiResponse::ok("API key revoked", Some(key))))
+}
@@ -0,0 +1,31 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody};
+use crate::models::api_key::query::find_api_keys;
+use crate::models::api_key::types::ApiKeyDto;
+
+/// GET /api/keys
+#[utoipa::path(
+    get,
+    path = "/api/keys",
+    responses(
+        (status = 200, description = "All API keys, revoked ones included; keys themselves are never returned", body = ApiResponse<Vec<ApiKeyDto>>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `admin` scope", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Auth"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+) -> Result<Json<ApiResponse<Vec<ApiKeyDto>>>, ApiError> {
+    let keys = find_api_keys(db.get_pool()).await.map_err(|err| {
+        tracing::error!(error = %err, "failed to fetch API keys");
+        ApiError::Internal
+    })?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(keys))))
+}
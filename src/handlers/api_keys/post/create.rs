@@ -0,0 +1,79 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody, FieldError, extract};
+use crate::middleware::auth::{KEY_PREFIX_LEN, Scope, generate_key, hash_key};
+use crate::models::api_key::query::insert_api_key;
+use crate::models::api_key::types::{ApiKeyCreateDto, ApiKeyCreatedDto, MAX_KEY_NAME_LEN};
+
+/// POST /api/keys
+#[utoipa::path(
+    post,
+    path = "/api/keys",
+    request_body = ApiKeyCreateDto,
+    responses(
+        (status = 200, description = "Created key; `key` is only shown in this response", body = ApiResponse<ApiKeyCreatedDto>),
+        (status = 400, description = "Invalid name or scopes", body = ApiResponse<ErrorBody>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `admin` scope", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Auth"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    extract::Json(payload): extract::Json<ApiKeyCreateDto>,
+) -> Result<Json<ApiResponse<ApiKeyCreatedDto>>, ApiError> {
+    let name = payload.name.trim();
+    let mut errors = Vec::new();
+    if name.is_empty() || name.chars().count() > MAX_KEY_NAME_LEN {
+        errors.push(FieldError::new(
+            "name",
+            format!("must be between 1 and {} characters", MAX_KEY_NAME_LEN),
+        ));
+    }
+    if payload.scopes.is_empty() {
+        errors.push(FieldError::new("scopes", "must not be empty"));
+    }
+    if let Some(unknown) = payload.scopes.iter().find(|s| Scope::parse(s).is_none()) {
+        errors.push(FieldError::new(
+            "scopes",
+            format!(
+                "unknown scope `{}`; allowed: {}",
+                unknown,
+                Scope::ALLOWED.join(", ")
+            ),
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let mut scopes = payload.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    let key = generate_key().map_err(|err| {
+        tracing::error!(error = %err, "failed to generate API key");
+        ApiError::Internal
+    })?;
+    let api_key = insert_api_key(
+        db.get_pool(),
+        name,
+        &hash_key(&key),
+        &key[..KEY_PREFIX_LEN],
+        &scopes,
+    )
+    .await
+    .map_err(|err| {
+        tracing::error!(error = %err, "failed to store API key");
+        ApiError::Internal
+    })?;
+
+    Ok(Json(ApiResponse::ok(
+        "API key created",
+        Some(ApiKeyCreatedDto { api_key, key }),
+    )))
+}
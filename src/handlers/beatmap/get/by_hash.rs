@@ -0,0 +1,184 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use db::models::beatmaps::pending_beatmap::PendingBeatmapRow;
+use dto::common::ApiResponse;
+use dto::models::beatmaps::simple::query::find_by_osu_ids::find_by_osu_ids;
+use dto::models::beatmaps::simple::types::Beatmapset;
+use sqlx::PgPool;
+
+use crate::error::extract::Path;
+use crate::error::{ApiError, ErrorBody};
+use crate::handlers::pending_beatmap::post::status_by_hashes::parse_hashes;
+use crate::models::beatmap::query::find_by_hashes;
+use crate::models::beatmap::types::{HashLookupDto, LookupStatus};
+use crate::models::pending_beatmap::query::find_progress_by_hashes;
+use crate::models::pending_beatmap::types::{HashStatusDto, ProcessingState};
+
+/// Difficultés traitées, avec leur beatmapset tel que servi par `GET /api/beatmapsets/{osu_id}`
+async fn find_found(
+    pool: &PgPool,
+    hashes: &[String],
+) -> Result<HashMap<String, HashLookupDto>, sqlx::Error> {
+    let rows = find_by_hashes(pool, hashes).await?;
+
+    let mut set_ids: Vec<i32> = rows.iter().filter_map(|r| r.beatmapset_osu_id).collect();
+    set_ids.sort_unstable();
+    set_ids.dedup();
+    let sets: HashMap<i32, Beatmapset> = if set_ids.is_empty() {
+        HashMap::new()
+    } else {
+        find_by_osu_ids(pool, &set_ids, None)
+            .await?
+            .into_iter()
+            .filter_map(|set| Some((set.osu_id?, set)))
+            .collect()
+    };
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let set = sets.get(&row.beatmapset_osu_id?)?;
+            let beatmap = set.beatmaps.iter().find(|b| b.osu_id == row.osu_id)?;
+            let result = HashLookupDto::found(row.file_md5.clone(), beatmap.clone(), set.clone());
+            Some((row.file_md5, result))
+        })
+        .collect())
+}
+
+/// Statut d'un hash sans beatmap traitée, d'après sa dernière entrée dans la file
+fn lookup_status(progress: Option<&HashStatusDto>, enqueue: bool) -> LookupStatus {
+    match progress.map(|p| p.state) {
+        Some(ProcessingState::Failed) | None if enqueue => LookupStatus::Queued,
+        Some(ProcessingState::Failed) => LookupStatus::Failed,
+        Some(_) => LookupStatus::Pending,
+        None => LookupStatus::Unknown,
+    }
+}
+
+/// Résout des MD5 déjà validés : beatmap traitée, en file d'attente, en échec
+/// ou inconnue. Avec `enqueue`, les hashes inconnus ou en échec sont (re)mis en file.
+pub async fn lookup_hashes(
+    pool: &PgPool,
+    hashes: &[String],
+    enqueue: bool,
+) -> Result<Vec<HashLookupDto>, ApiError> {
+    let found = find_found(pool, hashes).await.map_err(|err| {
+        tracing::error!(error = %err, "failed to look up beatmaps by hash");
+        ApiError::Internal
+    })?;
+
+    let mut seen = HashSet::new();
+    let missing: Vec<String> = hashes
+        .iter()
+        .filter(|h| !found.contains_key(*h) && seen.insert(h.as_str()))
+        .cloned()
+        .collect();
+
+    let pending: HashMap<String, HashStatusDto> = if missing.is_empty() {
+        HashMap::new()
+    } else {
+        find_progress_by_hashes(pool, &missing)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err, "failed to fetch pending status by hashes");
+                ApiError::Internal
+            })?
+            .into_iter()
+            .filter(|row| row.pending_id.is_some())
+            .map(|row| (row.osu_hash.clone(), HashStatusDto::from(row)))
+            .collect()
+    };
+
+    let to_enqueue: Vec<PendingBeatmapRow> = missing
+        .iter()
+        .filter(|h| lookup_status(pending.get(*h), true) == LookupStatus::Queued)
+        .map(|h| PendingBeatmapRow {
+            id: 1,
+            osu_hash: h.clone(),
+            osu_id: None,
+            created_at: None,
+        })
+        .collect();
+    if enqueue && !to_enqueue.is_empty() {
+        PendingBeatmapRow::bulk_insert(pool, &to_enqueue)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = %err, "failed to enqueue unknown or failed hashes");
+                ApiError::Internal
+            })?;
+    }
+
+    Ok(hashes
+        .iter()
+        .map(|h| {
+            if let Some(result) = found.get(h) {
+                return result.clone();
+            }
+            let progress = pending.get(h);
+            let status = lookup_status(progress, enqueue);
+            HashLookupDto {
+                // Remis en file : l'échec précédent n'est plus d'actualité
+                progress: progress.filter(|_| status != LookupStatus::Queued).cloned(),
+                ..HashLookupDto::without_match(h.clone(), status)
+            }
+        })
+        .collect())
+}
+
+/// GET /api/beatmaps/by-hash/{md5}
+#[utoipa::path(
+    get,
+    path = "/api/beatmaps/by-hash/{md5}",
+    params(
+        ("md5" = String, Path, description = "MD5 of the .osu file", example = "d41d8cd98f00b204e9800998ecf8427e")
+    ),
+    responses(
+        (status = 200, description = "`found` with the difficulty and its beatmapset, `pending` with its queue progress, `failed` with the failure reason in `progress`, or `unknown`. Use `POST /api/beatmaps/by-hash` with `enqueue` to queue unknown or failed hashes", body = ApiResponse<HashLookupDto>),
+        (status = 400, description = "Malformed hash", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Beatmaps"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(md5): Path<String>,
+) -> Result<Json<ApiResponse<HashLookupDto>>, ApiError> {
+    let hashes = parse_hashes("md5", vec![md5])?;
+
+    let result = lookup_hashes(db.get_pool(), &hashes, false).await?.pop();
+
+    Ok(Json(ApiResponse::ok("ok", result)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(state: ProcessingState) -> HashStatusDto {
+        HashStatusDto {
+            state,
+            ..HashStatusDto::unknown("d41d8cd98f00b204e9800998ecf8427e".to_string(), None)
+        }
+    }
+
+    #[test]
+    fn failed_entries_are_surfaced_then_requeued() {
+        let failed = progress(ProcessingState::Failed);
+        assert_eq!(lookup_status(Some(&failed), false), LookupStatus::Failed);
+        assert_eq!(lookup_status(Some(&failed), true), LookupStatus::Queued);
+    }
+
+    #[test]
+    fn pending_entries_are_not_requeued() {
+        for state in [ProcessingState::Queued, ProcessingState::Processing] {
+            let pending = progress(state);
+            assert_eq!(lookup_status(Some(&pending), false), LookupStatus::Pending);
+            assert_eq!(lookup_status(Some(&pending), true), LookupStatus::Pending);
+        }
+        assert_eq!(lookup_status(None, false), LookupStatus::Unknown);
+        assert_eq!(lookup_status(None, true), LookupStatus::Queued);
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody};
+use crate::models::beatmap::query::find_by_osu_id;
+use crate::models::beatmap::types::BeatmapDetailDto;
+use crate::models::rate::query::find_rate_details;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BeatmapQuery {
+    /// Only keep ratings of this type
+    #[param(example = "overall")]
+    pub rating_type: Option<String>,
+}
+
+/// GET /api/beatmaps/{beatmap_osu_id}
+#[utoipa::path(
+    get,
+    path = "/api/beatmaps/{beatmap_osu_id}",
+    params(
+        ("beatmap_osu_id" = i32, Path, description = "Beatmap osu ID", example = 123456),
+        BeatmapQuery
+    ),
+    responses(
+        (status = 200, description = "Difficulty with its stored rates, ratings and skillsets, and a link to its beatmapset", body = BeatmapDetailDto),
+        (status = 304, description = "Not modified: `If-None-Match` matches the current ETag"),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Beatmap not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Beatmaps"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(beatmap_osu_id): Path<i32>,
+    Query(params): Query<BeatmapQuery>,
+) -> Result<Json<BeatmapDetailDto>, ApiError> {
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch beatmap with osu_id {}", beatmap_osu_id);
+        ApiError::Internal
+    };
+
+    let row = find_by_osu_id(pool, beatmap_osu_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Beatmap {} not found", beatmap_osu_id)))?;
+    let mut rates = find_rate_details(pool, row.id, None, None)
+        .await
+        .map_err(internal)?;
+
+    if let Some(rating_type) = &params.rating_type {
+        for rate in &mut rates {
+            rate.ratings.retain(|r| &r.rating_type == rating_type);
+        }
+    }
+
+    Ok(Json(BeatmapDetailDto::new(row, rates)))
+}
@@ -1,3 +1,3 @@
-pub mod list;
+pub mod by_hash;
 pub mod find_one;
-pub mod rate;
\ No newline at end of file
+pub mod similar;
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use dto::models::beatmaps::simple::query::find_by_osu_ids::find_by_osu_ids;
+use dto::models::beatmaps::simple::types::Beatmapset;
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::beatmap::query::{find_similar, find_similarity_source};
+use crate::models::beatmap::types::{SimilarBeatmapDto, SimilarBeatmapRow, SimilarityConstraints};
+use crate::models::beatmapset::query::{DEFAULT_RATING_TYPE, REFERENCE_CENTIRATE};
+use crate::models::beatmapset::types::RATING_TYPES;
+use crate::models::beatmapset::types::{CENTIRATE_RANGE, RateScope};
+
+/// Nombre de difficultés retournées sans `limit`
+const DEFAULT_LIMIT: i64 = 10;
+const MAX_LIMIT: i64 = 50;
+/// Les candidates ont un rating à ±15 % de celui de la source
+const RATING_WINDOW: f64 = 0.15;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SimilarQuery {
+    /// Number of difficulties to return (default 10, max 50)
+    #[param(example = 10)]
+    pub limit: Option<i64>,
+    /// Rate of the source difficulty whose profile is compared (default 100)
+    #[param(example = 100)]
+    pub centirate: Option<i32>,
+    /// Rating type included in the profile (default overall)
+    #[param(example = "overall")]
+    pub rating_type: Option<String>,
+    /// Only return difficulties with the same status as the source
+    #[param(example = true)]
+    pub same_status: Option<bool>,
+    /// Minimum BPM of returned difficulties
+    #[serde(alias = "bpm[min]", alias = "bpm.min")]
+    #[param(rename = "bpm[min]", example = 150.0)]
+    pub bpm_min: Option<f64>,
+    /// Maximum BPM of returned difficulties
+    #[serde(alias = "bpm[max]", alias = "bpm.max")]
+    #[param(rename = "bpm[max]", example = 220.0)]
+    pub bpm_max: Option<f64>,
+    /// Lowest rate of candidates; the closest rate of each difficulty is kept (default: source rate)
+    #[serde(alias = "rates[centirate_min]", alias = "rates.centirate_min")]
+    #[param(rename = "rates[centirate_min]", example = 90)]
+    pub centirate_min: Option<i32>,
+    /// Highest rate of candidates (default: source rate)
+    #[serde(alias = "rates[centirate_max]", alias = "rates.centirate_max")]
+    #[param(rename = "rates[centirate_max]", example = 120)]
+    pub centirate_max: Option<i32>,
+}
+
+impl SimilarQuery {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.limit.is_some_and(|v| !(1..=MAX_LIMIT).contains(&v)) {
+            errors.push(FieldError::new(
+                "limit",
+                format!("must be between 1 and {}", MAX_LIMIT),
+            ));
+        }
+        let (lowest, highest) = CENTIRATE_RANGE;
+        for (field, value) in [
+            ("centirate", self.centirate),
+            ("rates[centirate_min]", self.centirate_min),
+            ("rates[centirate_max]", self.centirate_max),
+        ] {
+            if value.is_some_and(|v| !(lowest..=highest).contains(&v)) {
+                errors.push(FieldError::new(
+                    field,
+                    format!("must be between {} and {}", lowest, highest),
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.centirate_min, self.centirate_max)
+            && min > max
+        {
+            errors.push(FieldError::new(
+                "rates[centirate_min]",
+                "must be lower than or equal to rates[centirate_max]",
+            ));
+        }
+        for (field, value) in [("bpm[min]", self.bpm_min), ("bpm[max]", self.bpm_max)] {
+            if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                errors.push(FieldError::new(field, "must be a non-negative number"));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.bpm_min, self.bpm_max)
+            && min > max
+        {
+            errors.push(FieldError::new(
+                "bpm[min]",
+                "must be lower than or equal to bpm[max]",
+            ));
+        }
+        if let Some(rating_type) = &self.rating_type
+            && !RATING_TYPES.contains(&rating_type.as_str())
+        {
+            errors.push(FieldError::new(
+                "rating_type",
+                format!(
+                    "unknown value `{}`; allowed: {}",
+                    rating_type,
+                    RATING_TYPES.join(", ")
+                ),
+            ));
+        }
+        errors
+    }
+
+    /// Fenêtre des candidats ; une borne absente prend le rate source
+    fn rate_scope(&self, centirate: i32) -> RateScope {
+        match (self.centirate_min, self.centirate_max) {
+            (None, None) => RateScope::At(centirate),
+            (Some(min), None) => RateScope::Within {
+                min,
+                max: min.max(centirate),
+            },
+            (None, Some(max)) => RateScope::Within {
+                min: max.min(centirate),
+                max,
+            },
+            (Some(min), Some(max)) => RateScope::Within { min, max },
+        }
+    }
+}
+
+/// Difficultés trouvées, avec leur beatmapset tel que servi par `GET /api/beatmapsets/{osu_id}`
+async fn load(
+    pool: &PgPool,
+    rows: Vec<SimilarBeatmapRow>,
+    rating_type: &str,
+) -> Result<Vec<SimilarBeatmapDto>, sqlx::Error> {
+    let mut set_ids: Vec<i32> = rows.iter().filter_map(|r| r.beatmapset_osu_id).collect();
+    set_ids.sort_unstable();
+    set_ids.dedup();
+    let sets: HashMap<i32, Beatmapset> = if set_ids.is_empty() {
+        HashMap::new()
+    } else {
+        find_by_osu_ids(pool, &set_ids, Some(rating_type.to_string()))
+            .await?
+            .into_iter()
+            .filter_map(|set| Some((set.osu_id?, set)))
+            .collect()
+    };
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let set = sets.get(&row.beatmapset_osu_id?)?;
+            let beatmap = set
+                .beatmaps
+                .iter()
+                .find(|b| b.osu_id == row.beatmap_osu_id)?;
+            Some(SimilarBeatmapDto::new(row, beatmap.clone(), set.clone()))
+        })
+        .collect())
+}
+
+/// GET /api/beatmaps/{beatmap_osu_id}/similar
+#[utoipa::path(
+    get,
+    path = "/api/beatmaps/{beatmap_osu_id}/similar",
+    params(
+        ("beatmap_osu_id" = i32, Path, description = "Beatmap osu ID", example = 123456),
+        SimilarQuery
+    ),
+    responses(
+        (status = 200, description = "Closest difficulties, most similar first. Candidates have a rating within 15% of the source; the distance is computed over the rating and skillsets, each dimension scaled by its spread among candidates", body = ApiResponse<Vec<SimilarBeatmapDto>>),
+        (status = 304, description = "Not modified: `If-None-Match` matches the current ETag"),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Beatmap or rate not found, or the source rate has no `rating_type` rating", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Beatmaps"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(beatmap_osu_id): Path<i32>,
+    Query(q): Query<SimilarQuery>,
+) -> Result<Json<ApiResponse<Vec<SimilarBeatmapDto>>>, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to find beatmaps similar to {}", beatmap_osu_id);
+        ApiError::Internal
+    };
+
+    let centirate = q.centirate.unwrap_or(REFERENCE_CENTIRATE);
+    let rating_type = q.rating_type.as_deref().unwrap_or(DEFAULT_RATING_TYPE);
+    let source = find_similarity_source(pool, beatmap_osu_id, centirate, rating_type)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Beatmap {} not found", beatmap_osu_id)))?;
+    let Some(rates_id) = source.rates_id else {
+        return Err(ApiError::not_found(format!(
+            "Rate {} not found for beatmap {}",
+            centirate, beatmap_osu_id
+        )));
+    };
+    if source.rating.is_none() {
+        return Err(ApiError::not_found(format!(
+            "No `{}` rating at rate {} for beatmap {}",
+            rating_type, centirate, beatmap_osu_id
+        )));
+    }
+
+    let constraints = SimilarityConstraints {
+        rating_type: rating_type.to_string(),
+        status: q
+            .same_status
+            .unwrap_or(false)
+            .then(|| source.status.clone()),
+        bpm_min: q.bpm_min,
+        bpm_max: q.bpm_max,
+        rate: q.rate_scope(centirate),
+        rating_window: RATING_WINDOW,
+        limit: q.limit.unwrap_or(DEFAULT_LIMIT),
+    };
+
+    let rows = find_similar(pool, &source, rates_id, &constraints)
+        .await
+        .map_err(internal)?;
+    let similar = load(pool, rows, rating_type).await.map_err(internal)?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(similar))))
+}
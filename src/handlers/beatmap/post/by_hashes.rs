@@ -0,0 +1,38 @@
+use axum::{Extension, Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody, extract};
+use crate::handlers::beatmap::get::by_hash::lookup_hashes;
+use crate::handlers::pending_beatmap::post::status_by_hashes::parse_hashes;
+use crate::middleware::auth::{ApiKey, Scope, ensure_scope};
+use crate::models::beatmap::types::{HashLookupDto, HashLookupRequestDto};
+
+/// POST /api/beatmaps/by-hash
+#[utoipa::path(
+    post,
+    path = "/api/beatmaps/by-hash",
+    request_body = HashLookupRequestDto,
+    responses(
+        (status = 200, description = "Lookup result of each hash, in request order", body = ApiResponse<Vec<HashLookupDto>>),
+        (status = 400, description = "Empty, oversized or malformed hash list", body = ApiResponse<ErrorBody>),
+        (status = 401, description = "`enqueue` without an API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "`enqueue` with a key lacking the `import` scope", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Beatmaps"
+)]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    key: Option<Extension<ApiKey>>,
+    extract::Json(payload): extract::Json<HashLookupRequestDto>,
+) -> Result<Json<ApiResponse<Vec<HashLookupDto>>>, ApiError> {
+    let hashes = parse_hashes("hashes", payload.hashes)?;
+    if payload.enqueue {
+        ensure_scope(key.as_deref(), Scope::Import)?;
+    }
+
+    let results = lookup_hashes(db.get_pool(), &hashes, payload.enqueue).await?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(results))))
+}
@@ -0,0 +1 @@
+pub mod by_hashes;
@@ -1,51 +1,191 @@
-use axum::{Json, extract::State, http::StatusCode};
+use std::collections::{HashMap, HashSet};
+
+use axum::{Json, extract::State};
 use db::db::DatabaseManager;
 use db::models::beatmaps::pending_beatmap::PendingBeatmapRow;
 use dto::common::ApiResponse;
-use dto::common::Empty;
 use dto::models::pending_beatmap::batch::types::BatchChecksumsRequestDto;
 use tracing::error;
 
+use crate::error::{ApiError, ErrorBody, extract};
+use crate::models::pending_beatmap::query::find_checksum_states;
+use crate::models::pending_beatmap::types::{
+    ChecksumStateRow, ImportReportDto, ImportResultDto, ImportStatus,
+};
+
+/// Nombre maximum de checksums traités par requête
+pub const MAX_BATCH_SIZE: usize = 500;
+
+/// Un checksum valide est un MD5 : 32 caractères hexadécimaux
+pub fn is_valid_md5(hash: &str) -> bool {
+    hash.len() == 32 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Statut connu sans la base : format, doublon, limite du batch ; `None` pour
+/// un hash à rechercher. Les doublons sont écartés avant la limite : seuls les
+/// hashes distincts la consomment.
+fn precheck(checksums: &[String]) -> Vec<Option<ImportStatus>> {
+    let mut seen = HashSet::new();
+    checksums
+        .iter()
+        .map(|h| {
+            if !is_valid_md5(h) {
+                Some(ImportStatus::InvalidFormat)
+            } else if !seen.insert(h.as_str()) {
+                Some(ImportStatus::Duplicate)
+            } else if seen.len() > MAX_BATCH_SIZE {
+                Some(ImportStatus::RejectedBatchLimit)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 #[utoipa::path(
     post,
         path = "/api/beatmaps/imports",
     request_body = BatchChecksumsRequestDto,
     responses(
-        (status = 200, description = "Checksums enqueued for processing", body = ApiResponse<Empty>),
-        (status = 400, description = "Bad request", body = ApiResponse<Empty>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Per-checksum import result, in submission order", body = ApiResponse<ImportReportDto>),
+        (status = 400, description = "Bad request", body = ApiResponse<ErrorBody>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `import` scope", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorBody>)
     ),
     tag = "Beatmaps"
 )]
 pub async fn handler(
     State(db): State<DatabaseManager>,
-    Json(payload): Json<BatchChecksumsRequestDto>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
-    let batch: Vec<PendingBeatmapRow> = payload
+    extract::Json(payload): extract::Json<BatchChecksumsRequestDto>,
+) -> Result<Json<ApiResponse<ImportReportDto>>, ApiError> {
+    if payload.checksums.is_empty() {
+        return Err(ApiError::validation("No checksum provided"));
+    }
+
+    let checksums: Vec<String> = payload
         .checksums
         .into_iter()
-        .take(500)
+        .map(|h| h.trim().to_ascii_lowercase())
+        .collect();
+
+    let precheck = precheck(&checksums);
+    let candidates: Vec<String> = checksums
+        .iter()
+        .zip(&precheck)
+        .filter(|(_, status)| status.is_none())
+        .map(|(h, _)| h.clone())
+        .collect();
+
+    let pool = db.get_pool();
+
+    let known: HashMap<String, ChecksumStateRow> = if candidates.is_empty() {
+        HashMap::new()
+    } else {
+        find_checksum_states(pool, &candidates)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "failed to fetch checksum states");
+                ApiError::Internal
+            })?
+            .into_iter()
+            .map(|row| (row.osu_hash.clone(), row))
+            .collect()
+    };
+
+    let batch: Vec<PendingBeatmapRow> = candidates
+        .iter()
+        .filter(|h| !known.contains_key(*h))
         .map(|h| PendingBeatmapRow {
             id: 1,
-            osu_hash: h,
+            osu_hash: h.clone(),
             osu_id: None,
             created_at: None,
         })
         .collect();
 
-    if batch.is_empty() {
-        return Ok(Json(ApiResponse::error("400", "No checksum provided")));
-    }
+    let inserted = if batch.is_empty() {
+        0
+    } else {
+        PendingBeatmapRow::bulk_insert(pool, &batch)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "bulk insert failed");
+                ApiError::Internal
+            })?
+    };
 
-    let inserted = PendingBeatmapRow::bulk_insert(db.get_pool(), &batch)
-        .await
-        .map_err(|e| {
-            error!(error = %e, "bulk insert failed");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let results = checksums
+        .into_iter()
+        .zip(precheck)
+        .map(|(checksum, precheck)| {
+            let (status, beatmap_osu_id) = match precheck {
+                Some(status) => (status, None),
+                None => match known.get(&checksum) {
+                    Some(ChecksumStateRow {
+                        beatmap_osu_id: Some(osu_id),
+                        ..
+                    }) => (ImportStatus::AlreadyProcessed, Some(*osu_id)),
+                    Some(ChecksumStateRow { pending: true, .. }) => {
+                        (ImportStatus::AlreadyPending, None)
+                    }
+                    _ => (ImportStatus::Queued, None),
+                },
+            };
+            ImportResultDto {
+                checksum,
+                status,
+                beatmap_osu_id,
+            }
+        })
+        .collect();
 
     Ok(Json(ApiResponse::ok(
         format!("{} checksums added to processing queue", inserted),
-        None,
+        Some(ImportReportDto {
+            queued: inserted as usize,
+            results,
+        }),
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md5(i: usize) -> String {
+        format!("{:032x}", i)
+    }
+
+    #[test]
+    fn md5_must_be_32_hex_chars() {
+        assert!(is_valid_md5("d41d8cd98f00b204e9800998ecf8427e"));
+        assert!(!is_valid_md5("d41d8cd98f00b204e9800998ecf8427"));
+        assert!(!is_valid_md5("z41d8cd98f00b204e9800998ecf8427e"));
+    }
+
+    #[test]
+    fn duplicates_are_reported_once_and_do_not_consume_the_limit() {
+        let mut checksums: Vec<String> = (0..MAX_BATCH_SIZE).map(md5).collect();
+        checksums.insert(1, md5(0));
+        checksums.push("not-a-hash".to_string());
+        checksums.push(md5(MAX_BATCH_SIZE));
+
+        let statuses = precheck(&checksums);
+        assert_eq!(statuses[0], None);
+        assert_eq!(statuses[1], Some(ImportStatus::Duplicate));
+        assert_eq!(statuses[MAX_BATCH_SIZE], None);
+        assert_eq!(
+            statuses[MAX_BATCH_SIZE + 1],
+            Some(ImportStatus::InvalidFormat)
+        );
+        assert_eq!(
+            statuses[MAX_BATCH_SIZE + 2],
+            Some(ImportStatus::RejectedBatchLimit)
+        );
+        assert_eq!(
+            statuses.iter().filter(|s| s.is_none()).count(),
+            MAX_BATCH_SIZE
+        );
+    }
+}
@@ -0,0 +1,716 @@
+//! Filtres communs aux listes de beatmapsets (`list`, `list_random`).
+//!
+//! `FilterParams` décrit les paramètres `rating[...]`, `skillset[...]`,
+//! `beatmap[...]`, `beatmap_technical[...]` et `rates[...]` une seule fois,
+//! pour serde comme pour l'OpenAPI ; `BeatmapFilters` les extrait et les
+//! valide avant de construire les `ListFilters` du modèle.
+//!
+//! `rating[rating_type]`, `skillset[pattern_type]` et `beatmap_technical[status]`
+//! acceptent plusieurs valeurs, répétées ou séparées par des virgules.
+//! Chaque skillset peut aussi avoir ses propres bornes :
+//! `skillset[jumpstream][min]=20&skillset[jumpstream][max]=24`.
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::{ApiError, FieldError};
+use crate::models::beatmapset::types::{
+    BEATMAP_STATUSES, CENTIRATE_RANGE, ListFilters, MatchMode, OD_RANGE, PATTERN_TYPES,
+    RATING_TYPES, RateScope, SkillsetBound,
+};
+
+/// Paramètres multi-valeurs : nom canonique et alias acceptés
+const MULTI_VALUE_PARAMS: [(&str, [&str; 2]); 3] = [
+    ("rating_type", ["rating[rating_type]", "rating.rating_type"]),
+    (
+        "pattern_type",
+        ["skillset[pattern_type]", "skillset.pattern_type"],
+    ),
+    (
+        "status",
+        ["beatmap_technical[status]", "beatmap_technical.status"],
+    ),
+];
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FilterParams {
+    // Rating
+    /// Rating types, comma-separated or repeated (OR): overall, etterna, osu, sunny
+    #[serde(alias = "rating[rating_type]", alias = "rating.rating_type")]
+    #[param(rename = "rating[rating_type]", example = "overall")]
+    pub rating_type: Option<String>,
+    /// Min rating
+    #[serde(alias = "rating[rating_min]", alias = "rating.rating_min")]
+    #[param(rename = "rating[rating_min]", example = 6.5)]
+    pub rating_min: Option<f64>,
+    /// Max rating
+    #[serde(alias = "rating[rating_max]", alias = "rating.rating_max")]
+    #[param(rename = "rating[rating_max]", example = 9.5)]
+    pub rating_max: Option<f64>,
+
+    // Skillset
+    /// Skillsets, comma-separated or repeated: stream, jumpstream, handstream, stamina, jackspeed, chordjack, technical. Each one gets `skillset[pattern_min]`/`skillset[pattern_max]`, or its own bounds with `skillset[<type>][min]`/`skillset[<type>][max]`
+    #[serde(alias = "skillset[pattern_type]", alias = "skillset.pattern_type")]
+    #[param(rename = "skillset[pattern_type]", example = "jumpstream,stream")]
+    pub pattern_type: Option<String>,
+    /// Skillset min
+    #[serde(alias = "skillset[pattern_min]", alias = "skillset.pattern_min")]
+    #[param(rename = "skillset[pattern_min]", example = 0.2)]
+    pub pattern_min: Option<f64>,
+    /// Skillset max
+    #[serde(alias = "skillset[pattern_max]", alias = "skillset.pattern_max")]
+    #[param(rename = "skillset[pattern_max]", example = 0.8)]
+    pub pattern_max: Option<f64>,
+    /// How several skillsets combine: all (AND, default) or any (OR)
+    #[serde(alias = "skillset[match]", alias = "skillset.match")]
+    #[param(rename = "skillset[match]", example = "any")]
+    pub skillset_match: Option<String>,
+    /// Bornes `skillset[<type>][min|max]`, lues hors serde
+    #[serde(skip)]
+    #[param(ignore)]
+    pub skillset_bounds: Vec<SkillsetBound>,
+
+    // Beatmap
+    /// Search on artist/title/creator
+    #[serde(alias = "beatmap[search_term]", alias = "beatmap.search_term")]
+    #[param(rename = "beatmap[search_term]", example = "Camellia")]
+    pub search_term: Option<String>,
+    /// Min total time (ms)
+    #[serde(alias = "beatmap[total_time_min]", alias = "beatmap.total_time_min")]
+    #[param(rename = "beatmap[total_time_min]", example = 60000)]
+    pub total_time_min: Option<i32>,
+    /// Max total time (ms)
+    #[serde(alias = "beatmap[total_time_max]", alias = "beatmap.total_time_max")]
+    #[param(rename = "beatmap[total_time_max]", example = 240000)]
+    pub total_time_max: Option<i32>,
+    /// Min BPM
+    #[serde(alias = "beatmap[bpm_min]", alias = "beatmap.bpm_min")]
+    #[param(rename = "beatmap[bpm_min]", example = 120.0)]
+    pub bpm_min: Option<f64>,
+    /// Max BPM
+    #[serde(alias = "beatmap[bpm_max]", alias = "beatmap.bpm_max")]
+    #[param(rename = "beatmap[bpm_max]", example = 220.0)]
+    pub bpm_max: Option<f64>,
+
+    // Beatmap Technical
+    /// Min Overall Difficulty (0-10)
+    #[serde(
+        alias = "beatmap_technical[od_min]",
+        alias = "beatmap_technical.od_min"
+    )]
+    #[param(rename = "beatmap_technical[od_min]", example = 5.0)]
+    pub od_min: Option<f64>,
+    /// Max Overall Difficulty (0-10)
+    #[serde(
+        alias = "beatmap_technical[od_max]",
+        alias = "beatmap_technical.od_max"
+    )]
+    #[param(rename = "beatmap_technical[od_max]", example = 10.0)]
+    pub od_max: Option<f64>,
+    /// Beatmap statuses, comma-separated or repeated (OR): graveyard, wip, pending, ranked, approved, qualified, loved
+    #[serde(
+        alias = "beatmap_technical[status]",
+        alias = "beatmap_technical.status"
+    )]
+    #[param(rename = "beatmap_technical[status]", example = "ranked,loved")]
+    pub status: Option<String>,
+
+    // Rates
+    /// Min drain time (seconds)
+    #[serde(alias = "rates[drain_time_min]", alias = "rates.drain_time_min")]
+    #[param(rename = "rates[drain_time_min]", example = 60)]
+    pub drain_time_min: Option<i32>,
+    /// Max drain time (seconds)
+    #[serde(alias = "rates[drain_time_max]", alias = "rates.drain_time_max")]
+    #[param(rename = "rates[drain_time_max]", example = 300)]
+    pub drain_time_max: Option<i32>,
+    /// Rate (50-200, default 100) at which rating, skillset and drain time filters and sorts apply
+    #[serde(alias = "rates[centirate]", alias = "rates.centirate")]
+    #[param(rename = "rates[centirate]", example = 120)]
+    pub centirate: Option<i32>,
+    /// Lowest rate of an "any rate" window; each listed beatmapset then gives `matching_centirates`, the rates of the window at which each difficulty (by osu! id) matches
+    #[serde(alias = "rates[centirate_min]", alias = "rates.centirate_min")]
+    #[param(rename = "rates[centirate_min]", example = 100)]
+    pub centirate_min: Option<i32>,
+    /// Highest rate of an "any rate" window
+    #[serde(alias = "rates[centirate_max]", alias = "rates.centirate_max")]
+    #[param(rename = "rates[centirate_max]", example = 150)]
+    pub centirate_max: Option<i32>,
+}
+
+/// Découpe une liste séparée par des virgules, sans doublons ni valeurs vides
+fn split_values(raw: Option<&str>) -> Vec<String> {
+    let mut values: Vec<String> = Vec::new();
+    for value in raw.unwrap_or_default().split(',').map(str::trim) {
+        if !value.is_empty() && !values.iter().any(|v| v == value) {
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// `skillset[<type>][min]` ou `skillset.<type>.max` : type et borne
+fn skillset_bound_key(key: &str) -> Option<(&str, &str)> {
+    let (pattern_type, bound) = match key.strip_prefix("skillset[") {
+        Some(rest) => rest.strip_suffix(']')?.split_once("][")?,
+        None => key.strip_prefix("skillset.")?.split_once('.')?,
+    };
+    matches!(bound, "min" | "max").then_some((pattern_type, bound))
+}
+
+/// Vérifie que chaque valeur fait partie des valeurs connues
+fn check_one_of(errors: &mut Vec<FieldError>, field: &str, values: &[String], allowed: &[&str]) {
+    for value in values {
+        if !allowed.contains(&value.as_str()) {
+            errors.push(FieldError::new(
+                field,
+                format!("unknown value `{}`; allowed: {}", value, allowed.join(", ")),
+            ));
+        }
+    }
+}
+
+/// Vérifie qu'une valeur est finie et comprise dans `[min, max]`
+fn check_domain(
+    errors: &mut Vec<FieldError>,
+    field: &str,
+    value: Option<f64>,
+    min: f64,
+    max: Option<f64>,
+) {
+    let Some(value) = value else {
+        return;
+    };
+    let message = match max {
+        _ if !value.is_finite() => "must be a finite number".to_string(),
+        Some(max) if value < min || value > max => format!("must be between {} and {}", min, max),
+        None if value < min => format!("must be greater than or equal to {}", min),
+        _ => return,
+    };
+    errors.push(FieldError::new(field, message));
+}
+
+/// Vérifie qu'une borne min ne dépasse pas la borne max
+fn check_range<T: PartialOrd + Copy>(
+    errors: &mut Vec<FieldError>,
+    (min_field, max_field): (&str, &str),
+    min: Option<T>,
+    max: Option<T>,
+) {
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        errors.push(FieldError::new(
+            min_field,
+            format!("must be lower than or equal to {}", max_field),
+        ));
+    }
+}
+
+impl FilterParams {
+    /// Lit la query string : regroupe les paramètres multi-valeurs et extrait
+    /// les bornes par skillset avant de déléguer le reste à serde
+    pub fn from_query(raw: &str) -> Result<Self, ApiError> {
+        let mut multi: Vec<(&str, Vec<String>)> = MULTI_VALUE_PARAMS
+            .iter()
+            .map(|(name, _)| (*name, Vec::new()))
+            .collect();
+        let mut bounds: Vec<(String, String, String)> = Vec::new();
+        let mut rest = form_urlencoded::Serializer::new(String::new());
+
+        for (key, value) in form_urlencoded::parse(raw.as_bytes()) {
+            let multi_index = MULTI_VALUE_PARAMS
+                .iter()
+                .position(|(name, aliases)| key == *name || aliases.contains(&key.as_ref()));
+            if let Some(index) = multi_index {
+                multi[index].1.push(value.into_owned());
+            } else if let Some((pattern_type, bound)) = skillset_bound_key(&key) {
+                bounds.push((
+                    pattern_type.to_string(),
+                    bound.to_string(),
+                    value.into_owned(),
+                ));
+            } else {
+                rest.append_pair(&key, &value);
+            }
+        }
+        for (name, values) in &multi {
+            if !values.is_empty() {
+                rest.append_pair(name, &values.join(","));
+            }
+        }
+
+        let mut params: Self = serde_urlencoded::from_str(&rest.finish()).map_err(|err| {
+            ApiError::validation(format!("Failed to deserialize query string: {}", err))
+        })?;
+
+        let mut errors = Vec::new();
+        for (pattern_type, bound, value) in bounds {
+            let field = format!("skillset[{}][{}]", pattern_type, bound);
+            let Ok(value) = value.trim().parse::<f64>() else {
+                errors.push(FieldError::new(field, "must be a number"));
+                continue;
+            };
+            let index = match params
+                .skillset_bounds
+                .iter()
+                .position(|b| b.pattern_type.as_deref() == Some(pattern_type.as_str()))
+            {
+                Some(index) => index,
+                None => {
+                    params.skillset_bounds.push(SkillsetBound {
+                        pattern_type: Some(pattern_type),
+                        min: None,
+                        max: None,
+                    });
+                    params.skillset_bounds.len() - 1
+                }
+            };
+            let entry = &mut params.skillset_bounds[index];
+            match bound.as_str() {
+                "min" => entry.min = Some(value),
+                _ => entry.max = Some(value),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ApiError::invalid_fields(errors));
+        }
+
+        Ok(params)
+    }
+
+    /// Liste des paramètres invalides : valeur inconnue, hors domaine ou intervalle inversé
+    pub fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        check_one_of(
+            &mut errors,
+            "rating[rating_type]",
+            &split_values(self.rating_type.as_deref()),
+            &RATING_TYPES,
+        );
+        check_one_of(
+            &mut errors,
+            "skillset[pattern_type]",
+            &split_values(self.pattern_type.as_deref()),
+            &PATTERN_TYPES,
+        );
+        check_one_of(
+            &mut errors,
+            "beatmap_technical[status]",
+            &split_values(self.status.as_deref()),
+            &BEATMAP_STATUSES,
+        );
+        if let Some(raw) = &self.skillset_match
+            && MatchMode::parse(raw).is_none()
+        {
+            errors.push(FieldError::new(
+                "skillset[match]",
+                format!("unknown value `{}`; allowed: all, any", raw),
+            ));
+        }
+
+        let (od_min, od_max) = OD_RANGE;
+        let bounds = [
+            ("rating[rating_min]", self.rating_min, 0.0, None),
+            ("rating[rating_max]", self.rating_max, 0.0, None),
+            ("skillset[pattern_min]", self.pattern_min, 0.0, None),
+            ("skillset[pattern_max]", self.pattern_max, 0.0, None),
+            (
+                "beatmap[total_time_min]",
+                self.total_time_min.map(f64::from),
+                0.0,
+                None,
+            ),
+            (
+                "beatmap[total_time_max]",
+                self.total_time_max.map(f64::from),
+                0.0,
+                None,
+            ),
+            ("beatmap[bpm_min]", self.bpm_min, 0.0, None),
+            ("beatmap[bpm_max]", self.bpm_max, 0.0, None),
+            (
+                "beatmap_technical[od_min]",
+                self.od_min,
+                od_min,
+                Some(od_max),
+            ),
+            (
+                "beatmap_technical[od_max]",
+                self.od_max,
+                od_min,
+                Some(od_max),
+            ),
+            (
+                "rates[drain_time_min]",
+                self.drain_time_min.map(f64::from),
+                0.0,
+                None,
+            ),
+            (
+                "rates[drain_time_max]",
+                self.drain_time_max.map(f64::from),
+                0.0,
+                None,
+            ),
+        ];
+        for (field, value, min, max) in bounds {
+            check_domain(&mut errors, field, value, min, max);
+        }
+
+        let (rate_min, rate_max) = CENTIRATE_RANGE;
+        for (field, value) in [
+            ("rates[centirate]", self.centirate),
+            ("rates[centirate_min]", self.centirate_min),
+            ("rates[centirate_max]", self.centirate_max),
+        ] {
+            check_domain(
+                &mut errors,
+                field,
+                value.map(f64::from),
+                f64::from(rate_min),
+                Some(f64::from(rate_max)),
+            );
+        }
+        if self.centirate.is_some()
+            && (self.centirate_min.is_some() || self.centirate_max.is_some())
+        {
+            errors.push(FieldError::new(
+                "rates[centirate]",
+                "cannot be combined with rates[centirate_min]/rates[centirate_max]",
+            ));
+        }
+
+        check_range(
+            &mut errors,
+            ("rating[rating_min]", "rating[rating_max]"),
+            self.rating_min,
+            self.rating_max,
+        );
+        check_range(
+            &mut errors,
+            ("skillset[pattern_min]", "skillset[pattern_max]"),
+            self.pattern_min,
+            self.pattern_max,
+        );
+        check_range(
+            &mut errors,
+            ("beatmap[total_time_min]", "beatmap[total_time_max]"),
+            self.total_time_min,
+            self.total_time_max,
+        );
+        check_range(
+            &mut errors,
+            ("beatmap[bpm_min]", "beatmap[bpm_max]"),
+            self.bpm_min,
+            self.bpm_max,
+        );
+        check_range(
+            &mut errors,
+            ("beatmap_technical[od_min]", "beatmap_technical[od_max]"),
+            self.od_min,
+            self.od_max,
+        );
+        check_range(
+            &mut errors,
+            ("rates[drain_time_min]", "rates[drain_time_max]"),
+            self.drain_time_min,
+            self.drain_time_max,
+        );
+        check_range(
+            &mut errors,
+            ("rates[centirate_min]", "rates[centirate_max]"),
+            self.centirate_min,
+            self.centirate_max,
+        );
+
+        for bound in &self.skillset_bounds {
+            let pattern_type = bound.pattern_type.as_deref().unwrap_or_default();
+            let min_field = format!("skillset[{}][min]", pattern_type);
+            let max_field = format!("skillset[{}][max]", pattern_type);
+            if !PATTERN_TYPES.contains(&pattern_type) {
+                errors.push(FieldError::new(
+                    format!("skillset[{}]", pattern_type),
+                    format!(
+                        "unknown skillset `{}`; allowed: {}",
+                        pattern_type,
+                        PATTERN_TYPES.join(", ")
+                    ),
+                ));
+            }
+            check_domain(&mut errors, &min_field, bound.min, 0.0, None);
+            check_domain(&mut errors, &max_field, bound.max, 0.0, None);
+            check_range(&mut errors, (&min_field, &max_field), bound.min, bound.max);
+        }
+
+        errors
+    }
+
+    /// Rate(s) demandés par `rates[centirate]` ou `rates[centirate_min|max]` ;
+    /// une fenêtre ouverte d'un côté s'étend jusqu'à la borne calculée
+    fn rate_scope(&self) -> Option<RateScope> {
+        let (rate_min, rate_max) = CENTIRATE_RANGE;
+        match (self.centirate, self.centirate_min, self.centirate_max) {
+            (Some(centirate), _, _) => Some(RateScope::At(centirate)),
+            (None, None, None) => None,
+            (None, min, max) => Some(RateScope::Within {
+                min: min.unwrap_or(rate_min),
+                max: max.unwrap_or(rate_max),
+            }),
+        }
+    }
+
+    pub fn into_filters(self) -> ListFilters {
+        let rate = self.rate_scope();
+
+        // Bornes communes pour chaque type listé, puis bornes propres à un skillset
+        let mut skillsets: Vec<SkillsetBound> = split_values(self.pattern_type.as_deref())
+            .into_iter()
+            .map(|pattern_type| SkillsetBound {
+                pattern_type: Some(pattern_type),
+                min: self.pattern_min,
+                max: self.pattern_max,
+            })
+            .collect();
+        if skillsets.is_empty() && (self.pattern_min.is_some() || self.pattern_max.is_some()) {
+            skillsets.push(SkillsetBound {
+                pattern_type: None,
+                min: self.pattern_min,
+                max: self.pattern_max,
+            });
+        }
+        for bound in self.skillset_bounds {
+            match skillsets
+                .iter_mut()
+                .find(|b| b.pattern_type == bound.pattern_type)
+            {
+                Some(existing) => {
+                    existing.min = bound.min.or(existing.min);
+                    existing.max = bound.max.or(existing.max);
+                }
+                None => skillsets.push(bound),
+            }
+        }
+
+        ListFilters {
+            search_term: self.search_term,
+            rating_types: split_values(self.rating_type.as_deref()),
+            rating_min: self.rating_min,
+            rating_max: self.rating_max,
+            skillsets,
+            skillset_match: self
+                .skillset_match
+                .as_deref()
+                .and_then(MatchMode::parse)
+                .unwrap_or_default(),
+            statuses: split_values(self.status.as_deref()),
+            total_time_min: self.total_time_min,
+            total_time_max: self.total_time_max,
+            bpm_min: self.bpm_min,
+            bpm_max: self.bpm_max,
+            od_min: self.od_min,
+            od_max: self.od_max,
+            drain_time_min: self.drain_time_min,
+            drain_time_max: self.drain_time_max,
+            rate,
+        }
+    }
+}
+
+/// Extracteur des filtres de liste ; le tri et la pagination restent à la charge du handler
+pub struct BeatmapFilters(pub ListFilters);
+
+impl<S> FromRequestParts<S> for BeatmapFilters
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let params = FilterParams::from_query(parts.uri.query().unwrap_or_default())?;
+
+        let errors = params.validate();
+        if !errors.is_empty() {
+            return Err(ApiError::invalid_fields(errors));
+        }
+
+        Ok(Self(params.into_filters()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filters(query: &str) -> ListFilters {
+        FilterParams::from_query(query).unwrap().into_filters()
+    }
+
+    #[test]
+    fn statuses_accept_repeated_and_comma_separated_values() {
+        let filters = filters(
+            "beatmap_technical[status]=ranked,loved&beatmap_technical%5Bstatus%5D=ranked&status=approved",
+        );
+        assert_eq!(filters.statuses, ["ranked", "loved", "approved"]);
+    }
+
+    #[test]
+    fn rating_types_are_split() {
+        let filters = filters("rating[rating_type]=overall,etterna&rating[rating_min]=20");
+        assert_eq!(filters.rating_types, ["overall", "etterna"]);
+        assert_eq!(filters.rating_min, Some(20.0));
+        assert_eq!(filters.primary_rating_type(), "overall");
+    }
+
+    #[test]
+    fn per_skillset_bounds_override_shared_bounds() {
+        let filters = filters(
+            "skillset[pattern_type]=jumpstream,stream&skillset[pattern_min]=10\
+             &skillset[jumpstream][min]=20&skillset[jumpstream][max]=24&skillset[match]=any",
+        );
+        assert_eq!(filters.skillset_match, MatchMode::Any);
+        assert_eq!(
+            filters.skillsets,
+            [
+                SkillsetBound {
+                    pattern_type: Some("jumpstream".to_string()),
+                    min: Some(20.0),
+                    max: Some(24.0),
+                },
+                SkillsetBound {
+                    pattern_type: Some("stream".to_string()),
+                    min: Some(10.0),
+                    max: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn bounds_without_type_apply_to_any_skillset() {
+        let filters = filters("skillset[pattern_max]=0.8");
+        assert_eq!(filters.skillset_match, MatchMode::All);
+        assert_eq!(
+            filters.skillsets,
+            [SkillsetBound {
+                pattern_type: None,
+                min: None,
+                max: Some(0.8),
+            }]
+        );
+    }
+
+    #[test]
+    fn unused_filters_stay_empty() {
+        let filters = filters("");
+        assert!(filters.rating_types.is_empty());
+        assert!(filters.skillsets.is_empty());
+        assert!(filters.statuses.is_empty());
+        assert_eq!(filters.rate, None);
+        assert_eq!(filters.primary_rating_type(), "overall");
+    }
+
+    #[test]
+    fn rate_window_is_closed_by_the_computed_range() {
+        assert_eq!(
+            filters("rates[centirate]=120").rate,
+            Some(RateScope::At(120))
+        );
+        assert_eq!(
+            filters("rates[centirate_min]=120").rate,
+            Some(RateScope::Within { min: 120, max: 200 })
+        );
+        assert_eq!(
+            filters("rates[centirate_max]=90").rate,
+            Some(RateScope::Within { min: 50, max: 90 })
+        );
+    }
+
+    #[test]
+    fn non_numeric_skillset_bound_is_rejected() {
+        assert!(FilterParams::from_query("skillset[stream][min]=abc").is_err());
+    }
+
+    fn invalid_fields(query: &str) -> Vec<String> {
+        FilterParams::from_query(query)
+            .unwrap()
+            .validate()
+            .into_iter()
+            .map(|e| e.field)
+            .collect()
+    }
+
+    #[test]
+    fn valid_filters_pass() {
+        assert!(
+            invalid_fields(
+                "rating[rating_type]=overall&rating[rating_min]=20&rating[rating_max]=24\
+                 &skillset[pattern_type]=jumpstream&beatmap_technical[status]=ranked,loved\
+                 &beatmap_technical[od_min]=0&beatmap_technical[od_max]=10&rates[centirate_min]=100"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn unknown_enum_values_are_reported_per_field() {
+        assert_eq!(
+            invalid_fields(
+                "rating[rating_type]=overall,typo&skillset[pattern_type]=jumps\
+                 &beatmap_technical[status]=rankd&skillset[match]=some"
+            ),
+            [
+                "rating[rating_type]",
+                "skillset[pattern_type]",
+                "beatmap_technical[status]",
+                "skillset[match]"
+            ]
+        );
+    }
+
+    #[test]
+    fn out_of_domain_values_are_reported() {
+        assert_eq!(
+            invalid_fields(
+                "beatmap_technical[od_max]=11&beatmap[total_time_min]=-1&rates[centirate]=300"
+            ),
+            [
+                "beatmap[total_time_min]",
+                "beatmap_technical[od_max]",
+                "rates[centirate]"
+            ]
+        );
+    }
+
+    #[test]
+    fn inverted_ranges_are_reported_on_the_min_field() {
+        assert_eq!(
+            invalid_fields("beatmap[bpm_min]=200&beatmap[bpm_max]=120"),
+            ["beatmap[bpm_min]"]
+        );
+        assert_eq!(
+            invalid_fields("skillset[stream][min]=24&skillset[stream][max]=20"),
+            ["skillset[stream][min]"]
+        );
+    }
+
+    #[test]
+    fn unknown_per_skillset_bound_is_reported() {
+        assert_eq!(
+            invalid_fields("skillset[streams][min]=1"),
+            ["skillset[streams]"]
+        );
+    }
+
+    #[test]
+    fn single_rate_and_window_are_exclusive() {
+        assert_eq!(
+            invalid_fields("rates[centirate]=120&rates[centirate_max]=150"),
+            ["rates[centirate]"]
+        );
+    }
+}
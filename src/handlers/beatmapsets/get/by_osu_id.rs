@@ -1,12 +1,12 @@
-use axum::{
-    Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
-};
+use axum::{Json, extract::State};
 use db::db::DatabaseManager;
+use dto::common::ApiResponse;
 use dto::models::beatmaps::simple::query::find_by_osu_id::find_by_osu_id;
 use serde::Deserialize;
 
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody};
+
 /// GET /api/beatmapsets/{osu_id}
 #[utoipa::path(
     get,
@@ -17,8 +17,10 @@ use serde::Deserialize;
     ),
     responses(
         (status = 200, description = "Beatmapset with difficulties", body = dto::models::beatmaps::simple::types::Beatmapset),
-        (status = 404, description = "Beatmapset not found"),
-        (status = 500, description = "Internal error")
+        (status = 304, description = "Not modified: `If-None-Match` matches the current ETag"),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Beatmapset not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
     ),
     tag = "Beatmaps"
 )]
@@ -27,15 +29,18 @@ pub async fn handler(
     State(db): State<DatabaseManager>,
     Path(osu_id): Path<i32>,
     Query(params): Query<BeatmapQuery>,
-) -> Result<Json<dto::models::beatmaps::simple::types::Beatmapset>, StatusCode> {
+) -> Result<Json<dto::models::beatmaps::simple::types::Beatmapset>, ApiError> {
     let pool = db.get_pool();
 
     match find_by_osu_id(pool, osu_id, params.rating_type).await {
         Ok(Some(beatmapset)) => Ok(Json(beatmapset)),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Ok(None) => Err(ApiError::not_found(format!(
+            "Beatmapset {} not found",
+            osu_id
+        ))),
         Err(err) => {
             tracing::error!(error = %err, "failed to fetch beatmapset with osu_id {}", osu_id);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::Internal)
         }
     }
 }
@@ -1,211 +1,323 @@
 use axum::{
     Json,
-    extract::{Query, State},
-    http::StatusCode,
+    extract::State,
+    response::{IntoResponse, Response},
 };
 use db::db::DatabaseManager;
 use dto::common::{PaginatedResponse, Pagination};
-use dto::filters::{
-    BeatmapFilter, BeatmapTechnicalFilter, Filters, RatesFilter, RatingFilter, SkillsetFilter,
-};
-use dto::models::beatmaps::short::query::{count_with_filters, find_all_with_filters};
-use dto::models::beatmaps::short::types::Beatmapset;
 use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::error::extract::Query;
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::handlers::beatmapsets::filters::{BeatmapFilters, FilterParams};
+use crate::models::beatmapset::query::{count_beatmapsets, find_beatmapset_page, load_list_items};
+use crate::models::beatmapset::types::{
+    BeatmapsetCursorPage, BeatmapsetListItem, BeatmapsetPageRow, Cursor, CursorDirection,
+    CursorPagination, ListFilters, PageStart, Sort, SortField, SortOrder,
+};
+
+const DEFAULT_PER_PAGE: usize = 20;
+pub const MAX_PER_PAGE: usize = 100;
 
 /// GET /api/beatmaps
 #[utoipa::path(
     get,
     path = "/api/beatmapsets",
-    params(
-        ("page" = Option<usize>, Query, description = "Page index (0-based)", example = 0),
-        ("per_page" = Option<usize>, Query, description = "Items per page", example = 20),
-        ("rating[rating_type]" = Option<String>, Query, description = "Rating type filter", example = "overall"),
-        ("rating[rating_min]" = Option<f64>, Query, description = "Min rating", example = 6.5),
-        ("rating[rating_max]" = Option<f64>, Query, description = "Max rating", example = 9.5),
-        ("skillset[pattern_type]" = Option<String>, Query, description = "Skillset type filter", example = "stream"),
-        ("skillset[pattern_min]" = Option<f64>, Query, description = "Skillset min", example = 0.2),
-        ("skillset[pattern_max]" = Option<f64>, Query, description = "Skillset max", example = 0.8),
-        ("beatmap[search_term]" = Option<String>, Query, description = "Search on artist/title/creator", example = "Camellia"),
-        ("beatmap[total_time_min]" = Option<i32>, Query, description = "Min total time (ms)", example = 60000),
-        ("beatmap[total_time_max]" = Option<i32>, Query, description = "Max total time (ms)", example = 240000),
-        ("beatmap[bpm_min]" = Option<f64>, Query, description = "Min BPM", example = 120.0),
-        ("beatmap[bpm_max]" = Option<f64>, Query, description = "Max BPM", example = 220.0),
-        ("beatmap_technical[od_min]" = Option<f64>, Query, description = "Min Overall Difficulty", example = 5.0),
-        ("beatmap_technical[od_max]" = Option<f64>, Query, description = "Max Overall Difficulty", example = 10.0),
-        ("beatmap_technical[status]" = Option<String>, Query, description = "Beatmap status", example = "ranked"),
-        ("rates[drain_time_min]" = Option<i32>, Query, description = "Min drain time (seconds)", example = 60),
-        ("rates[drain_time_max]" = Option<i32>, Query, description = "Max drain time (seconds)", example = 300)
-    ),
+    params(BeatmapListQuery, FilterParams),
     responses(
-        (status = 200, description = "List beatmaps", body = dto::common::PaginatedResponse<dto::models::beatmaps::short::types::Beatmapset>),
-        (status = 500, description = "Internal error")
+        (status = 200, description = "List beatmaps. With `cursor`, the body is a `BeatmapsetCursorPage` whose pagination carries `next_cursor`/`prev_cursor` and, unless `include_total=false`, `total`", body = dto::common::PaginatedResponse<BeatmapsetListItem>),
+        (status = 304, description = "Not modified: `If-None-Match` matches the current ETag"),
+        (status = 400, description = "Invalid parameters", body = dto::common::ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = dto::common::ApiResponse<ErrorBody>)
     ),
     tag = "Beatmaps"
 )]
 #[axum::debug_handler]
 pub async fn handler(
     State(db): State<DatabaseManager>,
+    BeatmapFilters(filters): BeatmapFilters,
     Query(q): Query<BeatmapListQuery>,
-) -> Result<Json<PaginatedResponse<Beatmapset>>, StatusCode> {
-    let filters = q.into_filters();
+) -> Result<Response, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+    let sort = parse_sort(q.sort.as_deref(), q.order.as_deref(), &filters)?;
+
     let pool = db.get_pool();
-    let page = filters.page.unwrap_or(0) as u32;
-    let per_page = filters.per_page.unwrap_or(20) as u32;
-    let total = match count_with_filters(pool, &filters).await {
-        Ok(t) => t as u64,
-        Err(err) => {
-            tracing::error!(error = %err, "failed to count beatmaps list");
-            0
-        }
-    };
-    match find_all_with_filters(pool, filters).await {
-        Ok(list) => Ok(Json(PaginatedResponse {
+    let per_page = q.per_page.map_or(DEFAULT_PER_PAGE, |p| {
+        p.clamp(1, MAX_PER_PAGE as i64) as usize
+    });
+
+    let Some(raw_cursor) = q.cursor.as_deref() else {
+        let page = q.page.unwrap_or(0);
+        let offset = page
+            .checked_mul(per_page as i64)
+            .ok_or_else(|| ApiError::validation("`page` is too large"))?;
+
+        let total = count(pool, &filters).await?;
+        let rows = find_page(pool, &filters, &sort, &PageStart::Offset(offset), per_page).await?;
+
+        return Ok(Json(PaginatedResponse {
             message: "ok".to_string(),
             status: "200".to_string(),
-            data: list,
+            data: load(pool, &rows, &filters).await?,
             pagination: Pagination {
-                page,
-                per_page,
-                total,
+                page: page as u32,
+                per_page: per_page as u32,
+                total: total as u64,
             },
-        })),
-        Err(err) => {
-            tracing::error!(error = %err, "failed to fetch beatmaps list");
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+        .into_response());
+    };
+
+    if q.page.is_some() {
+        return Err(ApiError::validation(
+            "Use either `page` or `cursor`, not both",
+        ));
+    }
+    let cursor = match raw_cursor {
+        "" => None,
+        raw => Some(Cursor::decode(raw).ok_or_else(|| ApiError::validation("Invalid cursor"))?),
+    };
+    if cursor
+        .as_ref()
+        .is_some_and(|c| c.sort != sort.fingerprint())
+    {
+        return Err(ApiError::validation(
+            "Cursor was issued for a different sort; restart from the first page",
+        ));
+    }
+    let backwards = cursor
+        .as_ref()
+        .is_some_and(|c| c.direction == CursorDirection::Prev);
+    let from_cursor = cursor.is_some();
+    let start = match cursor {
+        Some(cursor) => PageStart::After(cursor),
+        None => PageStart::Offset(0),
+    };
+
+    let total = if q.include_total.unwrap_or(true) {
+        Some(count(pool, &filters).await? as u64)
+    } else {
+        None
+    };
+
+    // Une ligne de plus pour savoir s'il reste une page
+    let mut rows = find_page(pool, &filters, &sort, &start, per_page + 1).await?;
+    let has_more = rows.len() > per_page;
+    if has_more {
+        if backwards {
+            rows.remove(0);
+        } else {
+            rows.truncate(per_page);
         }
     }
+    let (has_next, has_prev) = if backwards {
+        (true, has_more)
+    } else {
+        (has_more, from_cursor)
+    };
+
+    let next_cursor = rows
+        .last()
+        .filter(|_| has_next)
+        .map(|row| Cursor::at(row, &sort, CursorDirection::Next).encode());
+    let prev_cursor = rows
+        .first()
+        .filter(|_| has_prev)
+        .map(|row| Cursor::at(row, &sort, CursorDirection::Prev).encode());
+
+    Ok(Json(BeatmapsetCursorPage {
+        message: "ok".to_string(),
+        status: "200".to_string(),
+        data: load(pool, &rows, &filters).await?,
+        pagination: CursorPagination {
+            per_page: per_page as u32,
+            total,
+            next_cursor,
+            prev_cursor,
+        },
+    })
+    .into_response())
+}
+
+async fn count(pool: &PgPool, filters: &ListFilters) -> Result<i64, ApiError> {
+    count_beatmapsets(pool, filters).await.map_err(|err| {
+        tracing::error!(error = %err, "failed to count beatmaps list");
+        ApiError::Internal
+    })
+}
+
+async fn find_page(
+    pool: &PgPool,
+    filters: &ListFilters,
+    sort: &Sort,
+    start: &PageStart,
+    limit: usize,
+) -> Result<Vec<BeatmapsetPageRow>, ApiError> {
+    find_beatmapset_page(pool, filters, sort, start, limit as i64)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to fetch beatmaps list");
+            ApiError::Internal
+        })
+}
+
+/// Beatmapsets de la page, dans l'ordre du tri
+async fn load(
+    pool: &PgPool,
+    rows: &[BeatmapsetPageRow],
+    filters: &ListFilters,
+) -> Result<Vec<BeatmapsetListItem>, ApiError> {
+    let osu_ids: Vec<i32> = rows.iter().filter_map(|row| row.osu_id).collect();
+    load_list_items(pool, &osu_ids, filters)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to fetch beatmaps of list page");
+            ApiError::Internal
+        })
+}
+
+/// Valide `sort`/`order` ; un champ inconnu renvoie la liste des champs autorisés.
+/// Sans `sort`, la liste suit l'ordre d'insertion.
+fn parse_sort(
+    field: Option<&str>,
+    order: Option<&str>,
+    filters: &ListFilters,
+) -> Result<Sort, ApiError> {
+    let Some(raw) = field else {
+        return Ok(Sort::by_id());
+    };
+    let field = SortField::parse(raw).ok_or_else(|| {
+        ApiError::invalid_fields(vec![FieldError::new(
+            "sort",
+            format!(
+                "unknown sort field `{}`; allowed: {}",
+                raw,
+                SortField::ALLOWED.join(", ")
+            ),
+        )])
+    })?;
+    let order = match order {
+        None => field.default_order(),
+        Some(raw) => SortOrder::parse(raw).ok_or_else(|| {
+            ApiError::invalid_fields(vec![FieldError::new(
+                "order",
+                format!("unknown order `{}`; allowed: asc, desc", raw),
+            )])
+        })?,
+    };
+
+    let pattern_type = filters.primary_pattern_type().map(str::to_string);
+    if field == SortField::Skillset && pattern_type.is_none() {
+        return Err(ApiError::invalid_fields(vec![FieldError::new(
+            "sort",
+            "sorting by skillset requires `skillset[pattern_type]`",
+        )]));
+    }
+
+    Ok(Sort {
+        field,
+        order,
+        rating_type: filters.primary_rating_type().to_string(),
+        pattern_type,
+        rate: filters.rate,
+    })
 }
 
-#[derive(Debug, Deserialize)]
+/// Pagination et tri ; les filtres sont décrits par `FilterParams`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct BeatmapListQuery {
-    // Pagination
-    pub page: Option<usize>,
-    pub per_page: Option<usize>,
-
-    // Rating
-    #[serde(alias = "rating[rating_type]", alias = "rating.rating_type")]
-    pub rating_type: Option<String>,
-    #[serde(alias = "rating[rating_min]", alias = "rating.rating_min")]
-    pub rating_min: Option<f64>,
-    #[serde(alias = "rating[rating_max]", alias = "rating.rating_max")]
-    pub rating_max: Option<f64>,
-
-    // Skillset
-    #[serde(alias = "skillset[pattern_type]", alias = "skillset.pattern_type")]
-    pub pattern_type: Option<String>,
-    #[serde(alias = "skillset[pattern_min]", alias = "skillset.pattern_min")]
-    pub pattern_min: Option<f64>,
-    #[serde(alias = "skillset[pattern_max]", alias = "skillset.pattern_max")]
-    pub pattern_max: Option<f64>,
-
-    // Beatmap
-    #[serde(alias = "beatmap[search_term]", alias = "beatmap.search_term")]
-    pub search_term: Option<String>,
-    #[serde(alias = "beatmap[total_time_min]", alias = "beatmap.total_time_min")]
-    pub total_time_min: Option<i32>,
-    #[serde(alias = "beatmap[total_time_max]", alias = "beatmap.total_time_max")]
-    pub total_time_max: Option<i32>,
-    #[serde(alias = "beatmap[bpm_min]", alias = "beatmap.bpm_min")]
-    pub bpm_min: Option<f64>,
-    #[serde(alias = "beatmap[bpm_max]", alias = "beatmap.bpm_max")]
-    pub bpm_max: Option<f64>,
-
-    // Beatmap Technical
-    #[serde(
-        alias = "beatmap_technical[od_min]",
-        alias = "beatmap_technical.od_min"
-    )]
-    pub od_min: Option<f64>,
-    #[serde(
-        alias = "beatmap_technical[od_max]",
-        alias = "beatmap_technical.od_max"
-    )]
-    pub od_max: Option<f64>,
-    #[serde(
-        alias = "beatmap_technical[status]",
-        alias = "beatmap_technical.status"
-    )]
-    pub status: Option<String>,
-
-    // Rates
-    #[serde(alias = "rates[drain_time_min]", alias = "rates.drain_time_min")]
-    pub drain_time_min: Option<i32>,
-    #[serde(alias = "rates[drain_time_max]", alias = "rates.drain_time_max")]
-    pub drain_time_max: Option<i32>,
+    /// Page index (0-based). Cannot be combined with `cursor`
+    #[param(example = 0)]
+    pub page: Option<i64>,
+    /// Items per page (default 20, clamped to 1-100)
+    #[param(example = 20)]
+    pub per_page: Option<i64>,
+    /// Keyset pagination: empty for the first page, then `next_cursor`/`prev_cursor` from the previous response
+    pub cursor: Option<String>,
+    /// With `cursor`, count matching beatmapsets (default true). Disable for faster deep paging
+    #[param(example = false)]
+    pub include_total: Option<bool>,
+
+    /// Sort field: rating (uses rating[rating_type]), skillset (uses skillset[pattern_type]), bpm, drain_time (rating, skillset and drain_time at rates[centirate], or the best value in the rates window), od, created_at, ranked_date, title, artist. Without it, beatmapsets come in insertion order. Ties are broken by id
+    #[param(example = "rating")]
+    pub sort: Option<String>,
+    /// asc or desc (default: asc for title/artist, desc otherwise)
+    #[param(example = "desc")]
+    pub order: Option<String>,
 }
 
 impl BeatmapListQuery {
-    fn into_filters(self) -> Filters {
-        let rating =
-            if self.rating_type.is_some() || self.rating_min.is_some() || self.rating_max.is_some()
-            {
-                Some(RatingFilter {
-                    rating_type: self.rating_type,
-                    rating_min: self.rating_min,
-                    rating_max: self.rating_max,
-                })
-            } else {
-                None
-            };
-
-        let skillset = if self.pattern_type.is_some()
-            || self.pattern_min.is_some()
-            || self.pattern_max.is_some()
-        {
-            Some(SkillsetFilter {
-                pattern_type: self.pattern_type,
-                pattern_min: self.pattern_min,
-                pattern_max: self.pattern_max,
-            })
-        } else {
-            None
-        };
+    /// Page négative ; `per_page` est ramené dans 1..=100 plutôt que refusé
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.page.is_some_and(|p| p < 0) {
+            errors.push(FieldError::new(
+                "page",
+                "must be greater than or equal to 0",
+            ));
+        }
+        errors
+    }
+}
 
-        let beatmap = if self.search_term.is_some()
-            || self.total_time_min.is_some()
-            || self.total_time_max.is_some()
-            || self.bpm_min.is_some()
-            || self.bpm_max.is_some()
-        {
-            Some(BeatmapFilter {
-                search_term: self.search_term,
-                total_time_min: self.total_time_min,
-                total_time_max: self.total_time_max,
-                bpm_min: self.bpm_min,
-                bpm_max: self.bpm_max,
-            })
-        } else {
-            None
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::beatmapset::types::{RateScope, SkillsetBound};
 
-        let beatmap_technical =
-            if self.od_min.is_some() || self.od_max.is_some() || self.status.is_some() {
-                Some(BeatmapTechnicalFilter {
-                    od_min: self.od_min,
-                    od_max: self.od_max,
-                    status: self.status,
-                })
-            } else {
-                None
-            };
-
-        let rates = if self.drain_time_min.is_some() || self.drain_time_max.is_some() {
-            Some(RatesFilter {
-                drain_time_min: self.drain_time_min,
-                drain_time_max: self.drain_time_max,
-            })
-        } else {
-            None
+    fn error_fields(err: ApiError) -> Vec<String> {
+        match err {
+            ApiError::Validation { fields, .. } => fields.into_iter().map(|e| e.field).collect(),
+            _ => panic!("expected invalid fields"),
+        }
+    }
+
+    #[test]
+    fn no_sort_means_insertion_order() {
+        let sort = parse_sort(None, Some("desc"), &ListFilters::default()).unwrap();
+        assert_eq!(sort, Sort::by_id());
+    }
+
+    #[test]
+    fn sort_follows_the_filters() {
+        let filters = ListFilters {
+            rating_types: vec!["etterna".to_string()],
+            skillsets: vec![SkillsetBound {
+                pattern_type: Some("stream".to_string()),
+                min: None,
+                max: None,
+            }],
+            rate: Some(RateScope::At(120)),
+            ..Default::default()
         };
+        let sort = parse_sort(Some("skillset"), None, &filters).unwrap();
+        assert_eq!(sort.field, SortField::Skillset);
+        assert_eq!(sort.order, SortOrder::Desc);
+        assert_eq!(sort.rating_type, "etterna");
+        assert_eq!(sort.pattern_type.as_deref(), Some("stream"));
+        assert_eq!(sort.rate, Some(RateScope::At(120)));
+    }
 
-        Filters {
-            rating,
-            skillset,
-            beatmap,
-            beatmap_technical,
-            rates,
-            page: self.page,
-            per_page: self.per_page,
-        }
+    #[test]
+    fn unknown_sort_or_order_is_rejected() {
+        let filters = ListFilters::default();
+        assert_eq!(
+            error_fields(parse_sort(Some("difficulty"), None, &filters).unwrap_err()),
+            ["sort"]
+        );
+        assert_eq!(
+            error_fields(parse_sort(Some("bpm"), Some("up"), &filters).unwrap_err()),
+            ["order"]
+        );
+        assert_eq!(
+            error_fields(parse_sort(Some("skillset"), None, &filters).unwrap_err()),
+            ["sort"]
+        );
     }
 }
@@ -1,4 +1,5 @@
 pub mod batch;
+pub mod filters;
 pub mod get;
 pub mod rate;
 //pub mod post;
@@ -0,0 +1,203 @@
+//! Estimation d'un rate non stocké à partir des rates voisins.
+//!
+//! Chaque rating et skillset est interpolé linéairement entre les deux rates
+//! stockés qui encadrent le centirate demandé, ou extrapolé à partir des deux
+//! plus proches s'ils sont tous du même côté. Les timings ne sont pas estimés :
+//! ils se déduisent exactement du rate stocké le plus proche.
+
+use crate::models::rate::types::{
+    EstimateMethod, RateDetailDto, RateEstimate, RatingEntry, SkillsetEntry,
+};
+
+/// Au-delà de cette distance (en centirates) au rate stocké le plus proche,
+/// aucune estimation n'est proposée
+pub const MAX_ESTIMATE_DISTANCE: i32 = 50;
+
+/// Pénalité de confiance appliquée à une extrapolation
+const EXTRAPOLATION_PENALTY: f64 = 0.5;
+
+/// `rates` doit être trié par centirate croissant et ne pas contenir `centirate`
+pub fn estimate(rates: &[RateDetailDto], centirate: i32) -> Option<RateDetailDto> {
+    let split = rates.partition_point(|r| r.centirate < centirate);
+    let (a, b, method) = match (split, rates.len() - split) {
+        (0, n) if n >= 2 => (&rates[0], &rates[1], EstimateMethod::Extrapolated),
+        (n, 0) if n >= 2 => (&rates[n - 2], &rates[n - 1], EstimateMethod::Extrapolated),
+        (n, m) if n >= 1 && m >= 1 => (&rates[n - 1], &rates[n], EstimateMethod::Interpolated),
+        _ => return None,
+    };
+
+    let nearest = [a, b]
+        .into_iter()
+        .min_by_key(|r| (r.centirate - centirate).abs())?;
+    let distance = (nearest.centirate - centirate).abs();
+    if distance > MAX_ESTIMATE_DISTANCE {
+        return None;
+    }
+
+    let t = f64::from(centirate - a.centirate) / f64::from(b.centirate - a.centirate);
+    let lerp = |from: f64, to: f64| (from + (to - from) * t).max(0.0);
+
+    let ratings = a
+        .ratings
+        .iter()
+        .filter_map(|ra| {
+            let rb = b.ratings.iter().find(|r| r.rating_type == ra.rating_type)?;
+            Some(RatingEntry {
+                rates_id: 0,
+                rating_type: ra.rating_type.clone(),
+                rating: lerp(ra.rating, rb.rating),
+            })
+        })
+        .collect();
+    let skillsets = a
+        .skillsets
+        .iter()
+        .filter_map(|sa| {
+            let sb = b
+                .skillsets
+                .iter()
+                .find(|s| s.pattern_type == sa.pattern_type)?;
+            Some(SkillsetEntry {
+                rates_id: 0,
+                pattern_type: sa.pattern_type.clone(),
+                pattern: lerp(sa.pattern, sb.pattern),
+            })
+        })
+        .collect();
+
+    // Durées inversement proportionnelles au rate, BPM proportionnel
+    let scale = f64::from(nearest.centirate) / f64::from(centirate);
+
+    let penalty = match method {
+        EstimateMethod::Interpolated => 1.0,
+        EstimateMethod::Extrapolated => EXTRAPOLATION_PENALTY,
+    };
+    let confidence = (1.0 - f64::from(distance) / f64::from(MAX_ESTIMATE_DISTANCE)) * penalty;
+
+    Some(RateDetailDto {
+        centirate,
+        drain_time: (f64::from(nearest.drain_time) * scale).round() as i32,
+        total_time: (f64::from(nearest.total_time) * scale).round() as i32,
+        bpm: nearest.bpm / scale,
+        ratings,
+        skillsets,
+        estimated: true,
+        estimate: Some(RateEstimate {
+            method,
+            confidence: (confidence * 100.0).round() / 100.0,
+            source_centirates: vec![a.centirate, b.centirate],
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(centirate: i32, overall: f64, stream: f64) -> RateDetailDto {
+        RateDetailDto {
+            centirate,
+            drain_time: 120,
+            total_time: 125_000,
+            bpm: 180.0,
+            ratings: vec![RatingEntry {
+                rates_id: centirate,
+                rating_type: "overall".to_string(),
+                rating: overall,
+            }],
+            skillsets: vec![SkillsetEntry {
+                rates_id: centirate,
+                pattern_type: "stream".to_string(),
+                pattern: stream,
+            }],
+            estimated: false,
+            estimate: None,
+        }
+    }
+
+    fn overall(rate: &RateDetailDto) -> f64 {
+        rate.ratings[0].rating
+    }
+
+    #[test]
+    fn interpolates_between_surrounding_rates() {
+        let rates = [
+            rate(90, 18.0, 8.0),
+            rate(100, 20.0, 10.0),
+            rate(120, 24.0, 14.0),
+        ];
+        let estimated = estimate(&rates, 110).unwrap();
+
+        assert!((overall(&estimated) - 22.0).abs() < 1e-9);
+        assert!((estimated.skillsets[0].pattern - 12.0).abs() < 1e-9);
+        assert!(estimated.estimated);
+        let details = estimated.estimate.unwrap();
+        assert_eq!(details.method, EstimateMethod::Interpolated);
+        assert_eq!(details.source_centirates, [100, 120]);
+        assert_eq!(details.confidence, 0.8);
+    }
+
+    #[test]
+    fn extrapolates_from_the_two_nearest_rates_on_one_side() {
+        let rates = [
+            rate(90, 18.0, 8.0),
+            rate(100, 20.0, 10.0),
+            rate(110, 22.0, 12.0),
+        ];
+        let estimated = estimate(&rates, 130).unwrap();
+
+        assert!((overall(&estimated) - 26.0).abs() < 1e-9);
+        let details = estimated.estimate.unwrap();
+        assert_eq!(details.method, EstimateMethod::Extrapolated);
+        assert_eq!(details.source_centirates, [100, 110]);
+        // 20 centirates du plus proche, moitié de confiance pour une extrapolation
+        assert_eq!(details.confidence, 0.3);
+
+        let below = estimate(&rates, 80).unwrap();
+        assert!((overall(&below) - 16.0).abs() < 1e-9);
+        assert_eq!(below.estimate.unwrap().source_centirates, [90, 100]);
+    }
+
+    #[test]
+    fn extrapolated_values_never_go_negative() {
+        let rates = [rate(100, 2.0, 1.0), rate(110, 12.0, 6.0)];
+        let estimated = estimate(&rates, 60).unwrap();
+        assert_eq!(overall(&estimated), 0.0);
+        assert_eq!(estimated.skillsets[0].pattern, 0.0);
+    }
+
+    #[test]
+    fn refuses_beyond_max_distance() {
+        let rates = [rate(100, 20.0, 10.0), rate(110, 22.0, 12.0)];
+        assert!(estimate(&rates, 110 + MAX_ESTIMATE_DISTANCE).is_some());
+        assert!(estimate(&rates, 110 + MAX_ESTIMATE_DISTANCE + 1).is_none());
+        assert!(estimate(&rates, 100 - MAX_ESTIMATE_DISTANCE - 1).is_none());
+
+        let wide = [rate(50, 10.0, 5.0), rate(200, 40.0, 20.0)];
+        assert!(estimate(&wide, 125).is_none());
+    }
+
+    #[test]
+    fn needs_two_stored_rates() {
+        assert!(estimate(&[], 110).is_none());
+        assert!(estimate(&[rate(100, 20.0, 10.0)], 110).is_none());
+    }
+
+    #[test]
+    fn timings_follow_the_nearest_rate() {
+        let rates = [rate(100, 20.0, 10.0), rate(150, 30.0, 15.0)];
+        let estimated = estimate(&rates, 110).unwrap();
+        assert_eq!(estimated.drain_time, 109);
+        assert_eq!(estimated.total_time, 113_636);
+        assert!((estimated.bpm - 198.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn skips_values_missing_from_a_neighbour() {
+        let mut upper = rate(120, 24.0, 14.0);
+        upper.skillsets.clear();
+        let estimated = estimate(&[rate(100, 20.0, 10.0), upper], 110).unwrap();
+        assert_eq!(estimated.ratings.len(), 1);
+        assert!(estimated.skillsets.is_empty());
+    }
+}
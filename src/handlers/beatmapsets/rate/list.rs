@@ -0,0 +1,126 @@
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use dto::models::rate::{Rates, find_rate_by_beatmap_osu_id_and_centirate};
+use futures_util::future::try_join_all;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::beatmapset::query::DEFAULT_RATING_TYPE;
+use crate::models::beatmapset::types::RATING_TYPES;
+use crate::models::rate::query::{find_beatmap_id, find_compact_rates, find_rates};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RateListQuery {
+    /// Lowest centirate to return (inclusive)
+    #[param(example = 80)]
+    pub min: Option<i32>,
+    /// Highest centirate to return (inclusive)
+    #[param(example = 150)]
+    pub max: Option<i32>,
+    /// Return `{centirate, rating_type, rating}` per rate instead of the full `Rates` entries
+    #[param(example = true)]
+    pub compact: Option<bool>,
+    /// Rating type used in compact mode (default overall)
+    #[param(example = "overall")]
+    pub rating_type: Option<String>,
+}
+
+impl RateListQuery {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        for (field, value) in [("min", self.min), ("max", self.max)] {
+            if value.is_some_and(|v| v <= 0) {
+                errors.push(FieldError::new(field, "must be greater than 0"));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max)
+            && min > max
+        {
+            errors.push(FieldError::new("min", "must be lower than or equal to max"));
+        }
+        if let Some(rating_type) = &self.rating_type
+            && !RATING_TYPES.contains(&rating_type.as_str())
+        {
+            errors.push(FieldError::new(
+                "rating_type",
+                format!(
+                    "unknown value `{}`; allowed: {}",
+                    rating_type,
+                    RATING_TYPES.join(", ")
+                ),
+            ));
+        }
+        errors
+    }
+}
+
+/// GET /api/beatmaps/{beatmap_osu_id}/rates
+#[utoipa::path(
+    get,
+    path = "/api/beatmaps/{beatmap_osu_id}/rates",
+    params(
+        ("beatmap_osu_id" = i32, Path, description = "Beatmap osu ID", example = 123456),
+        RateListQuery
+    ),
+    responses(
+        (status = 200, description = "Stored rates by ascending centirate, each one the `Rates` returned by `GET /api/beatmaps/{beatmap_osu_id}/rates/{centirate}`. With `compact=true`, a `CompactRateDto` per rate instead", body = ApiResponse<Vec<Rates>>),
+        (status = 304, description = "Not modified: `If-None-Match` matches the current ETag"),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Beatmap not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Beatmaps"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(beatmap_osu_id): Path<i32>,
+    Query(q): Query<RateListQuery>,
+) -> Result<Response, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch rates for beatmap {}", beatmap_osu_id);
+        ApiError::Internal
+    };
+
+    let beatmap_id = find_beatmap_id(pool, beatmap_osu_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Beatmap {} not found", beatmap_osu_id)))?;
+
+    if q.compact.unwrap_or(false) {
+        let rating_type = q.rating_type.as_deref().unwrap_or(DEFAULT_RATING_TYPE);
+        let rates = find_compact_rates(pool, beatmap_id, q.min, q.max, rating_type)
+            .await
+            .map_err(internal)?;
+        return Ok(Json(ApiResponse::ok("ok", Some(rates))).into_response());
+    }
+
+    let centirates = find_rates(pool, beatmap_id, q.min, q.max)
+        .await
+        .map_err(internal)?;
+    let rates: Vec<Rates> =
+        try_join_all(centirates.iter().map(|row| {
+            find_rate_by_beatmap_osu_id_and_centirate(pool, beatmap_osu_id, row.centirate)
+        }))
+        .await
+        .map_err(internal)?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    Ok(Json(ApiResponse::ok("ok", Some(rates))).into_response())
+}
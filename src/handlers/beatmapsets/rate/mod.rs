@@ -1,10 +1,31 @@
+pub mod estimate;
+pub mod list;
+
 use axum::{
     Json,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::State,
+    response::{IntoResponse, Response},
 };
 use db::db::DatabaseManager;
+use dto::common::ApiResponse;
 use dto::models::rate::find_rate_by_beatmap_osu_id_and_centirate;
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::beatmapset::types::CENTIRATE_RANGE;
+use crate::models::rate::query::{find_beatmap_id, find_rate_details};
+use crate::models::rate::types::RateDetailDto;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RateQuery {
+    /// When the centirate isn't stored, estimate it from the nearest stored rates (within 50 centirates) instead of returning 404
+    #[param(example = true)]
+    pub estimate: Option<bool>,
+}
 
 /// GET /api/beatmaps/{beatmap_osu_id}/rates/{centirate}
 #[utoipa::path(
@@ -12,12 +33,15 @@ use dto::models::rate::find_rate_by_beatmap_osu_id_and_centirate;
     path = "/api/beatmaps/{beatmap_osu_id}/rates/{centirate}",
     params(
         ("beatmap_osu_id" = i32, Path, description = "Beatmap osu ID", example = 123456),
-        ("centirate" = i32, Path, description = "Centirate value", example = 100)
+        ("centirate" = i32, Path, description = "Centirate value", example = 100),
+        RateQuery
     ),
     responses(
-        (status = 200, description = "Rate data", body = dto::models::rate::Rates),
-        (status = 404, description = "Rate not found"),
-        (status = 500, description = "Internal error")
+        (status = 200, description = "Rate data. With `estimate=true` and no stored rate, a `RateDetailDto` instead, with `estimated: true` and `estimate` holding its `method`, `confidence` (0-1) and `source_centirates`", body = dto::models::rate::Rates),
+        (status = 304, description = "Not modified: `If-None-Match` matches the current ETag"),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Rate not found, and no estimate possible", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
     ),
     tag = "Beatmaps"
 )]
@@ -25,15 +49,67 @@ use dto::models::rate::find_rate_by_beatmap_osu_id_and_centirate;
 pub async fn handler(
     State(db): State<DatabaseManager>,
     Path((beatmap_osu_id, centirate)): Path<(i32, i32)>,
-) -> Result<Json<dto::models::rate::Rates>, StatusCode> {
+    Query(q): Query<RateQuery>,
+) -> Result<Response, ApiError> {
     let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch rate for beatmap {} with centirate {}", beatmap_osu_id, centirate);
+        ApiError::Internal
+    };
+
+    if let Some(rate) = find_rate_by_beatmap_osu_id_and_centirate(pool, beatmap_osu_id, centirate)
+        .await
+        .map_err(internal)?
+    {
+        return Ok(Json(rate).into_response());
+    }
+    if !q.estimate.unwrap_or(false) {
+        return Err(ApiError::not_found(format!(
+            "No rate {} for beatmap {}",
+            centirate, beatmap_osu_id
+        )));
+    }
+
+    let beatmap_id = find_beatmap_id(pool, beatmap_osu_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Beatmap {} not found", beatmap_osu_id)))?;
+    let rate = estimate_rate(pool, beatmap_id, beatmap_osu_id, centirate).await?;
+
+    Ok(Json(rate).into_response())
+}
 
-    match find_rate_by_beatmap_osu_id_and_centirate(pool, beatmap_osu_id, centirate).await {
-        Ok(Some(rate)) => Ok(Json(rate)),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(err) => {
-            tracing::error!(error = %err, "failed to fetch rate for beatmap {} with centirate {}", beatmap_osu_id, centirate);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+async fn estimate_rate(
+    pool: &PgPool,
+    beatmap_id: i32,
+    beatmap_osu_id: i32,
+    centirate: i32,
+) -> Result<RateDetailDto, ApiError> {
+    let (rate_min, rate_max) = CENTIRATE_RANGE;
+    if !(rate_min..=rate_max).contains(&centirate) {
+        return Err(ApiError::invalid_fields(vec![FieldError::new(
+            "centirate",
+            format!(
+                "estimates are only available between {} and {}",
+                rate_min, rate_max
+            ),
+        )]));
     }
+
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to load rates to estimate beatmap {} at {}", beatmap_osu_id, centirate);
+        ApiError::Internal
+    };
+    let rates = find_rate_details(pool, beatmap_id, None, None)
+        .await
+        .map_err(internal)?;
+
+    estimate::estimate(&rates, centirate).ok_or_else(|| {
+        ApiError::not_found(format!(
+            "No rate {} for beatmap {}, and not enough stored rates within {} centirates to estimate it",
+            centirate,
+            beatmap_osu_id,
+            estimate::MAX_ESTIMATE_DISTANCE
+        ))
+    })
 }
@@ -0,0 +1,62 @@
+use axum::{Extension, Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::{ApiResponse, Empty};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::cache;
+use crate::error::{ApiError, ErrorBody, extract};
+use crate::middleware::auth::{ApiKey, Scope, ensure_scope};
+use crate::models::beatmapset::query::find_beatmap_osu_ids;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CacheInvalidationRequestDto {
+    /// Beatmapset retraité ; absent = vide tout le cache (scope `admin` requis)
+    pub beatmapset_osu_id: Option<i32>,
+}
+
+/// POST /api/cache/invalidate - called by the import pipeline after re-processing a beatmapset
+#[utoipa::path(
+    post,
+    path = "/api/cache/invalidate",
+    request_body = CacheInvalidationRequestDto,
+    responses(
+        (status = 200, description = "Entries invalidated", body = ApiResponse<Empty>),
+        (status = 400, description = "Bad request", body = ApiResponse<ErrorBody>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `import` scope, or the `admin` scope when `beatmapset_osu_id` is omitted", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Cache"
+)]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    key: Option<Extension<ApiKey>>,
+    extract::Json(payload): extract::Json<CacheInvalidationRequestDto>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    if payload.beatmapset_osu_id.is_none() {
+        ensure_scope(key.as_deref(), Scope::Admin)?;
+    }
+
+    let Some(cache) = cache::get() else {
+        return Ok(Json(ApiResponse::ok("Response cache is disabled", None)));
+    };
+
+    let Some(beatmapset_osu_id) = payload.beatmapset_osu_id else {
+        cache.invalidate_all();
+        return Ok(Json(ApiResponse::ok("Cache cleared", None)));
+    };
+
+    let beatmap_osu_ids = find_beatmap_osu_ids(db.get_pool(), beatmapset_osu_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to fetch beatmaps of beatmapset {}", beatmapset_osu_id);
+            ApiError::Internal
+        })?;
+    cache.invalidate_beatmapset(beatmapset_osu_id, &beatmap_osu_ids);
+
+    Ok(Json(ApiResponse::ok(
+        format!("Cache invalidated for beatmapset {}", beatmapset_osu_id),
+        None,
+    )))
+}
@@ -0,0 +1,2 @@
+pub mod invalidate;
+pub mod stats;
@@ -0,0 +1,21 @@
+use axum::Json;
+use dto::common::ApiResponse;
+
+use crate::cache::{self, CacheStats};
+use crate::error::{ApiError, ErrorBody};
+
+/// GET /api/cache/stats
+#[utoipa::path(
+    get,
+    path = "/api/cache/stats",
+    responses(
+        (status = 200, description = "Response cache counters", body = ApiResponse<CacheStats>),
+        (status = 404, description = "Cache disabled", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Cache"
+)]
+pub async fn handler() -> Result<Json<ApiResponse<CacheStats>>, ApiError> {
+    let cache = cache::get().ok_or_else(|| ApiError::not_found("Response cache is disabled"))?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(cache.stats()))))
+}
@@ -7,6 +7,13 @@
 // pub mod user;
 // pub mod product;
 
+pub mod api_keys;
+pub mod beatmap;
 pub mod beatmapsets;
+pub mod cache;
 pub mod help;
 pub mod pending_beatmap;
+pub mod players;
+pub mod recommendations;
+pub mod scores;
+pub mod weekly;
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::{Extensions, HeaderMap},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use futures_util::stream::{self, Stream, StreamExt};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use sqlx::PgPool;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::error::extract::Query;
+use crate::error::{ApiError, ErrorBody};
+use crate::handlers::pending_beatmap::post::status_by_hashes::{MAX_HASHES, parse_hashes};
+use crate::middleware::rate_limit::client_key;
+use crate::models::pending_beatmap::query::{find_progress_by_hashes, find_progress_by_osu_ids};
+use crate::models::pending_beatmap::types::HashStatusDto;
+
+/// Intervalle entre deux lectures de la file
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Durée maximale d'un flux ; le client se reconnecte avec `Last-Event-ID`
+const MAX_STREAM_LIFETIME: Duration = Duration::from_secs(30 * 60);
+
+/// Flux ouverts simultanément, toutes connexions confondues
+const MAX_STREAMS: usize = 256;
+
+/// Flux ouverts simultanément par un même client (clé d'API, sinon IP)
+const MAX_STREAMS_PER_CLIENT: usize = 4;
+
+/// Délai suggéré avant de réessayer quand tous les flux sont occupés
+const STREAMS_FULL_RETRY_AFTER: u64 = 5;
+
+static OPEN_STREAMS: Lazy<Arc<Semaphore>> = Lazy::new(|| Arc::new(Semaphore::new(MAX_STREAMS)));
+
+/// Nombre de flux ouverts par client ; un client sans flux n'y figure pas
+static CLIENT_STREAMS: Lazy<Mutex<HashMap<String, usize>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// MD5 séparés par des virgules
+    pub hashes: Option<String>,
+    /// Ids de beatmap osu! séparés par des virgules
+    pub ids: Option<String>,
+}
+
+/// GET /api/pending_beatmap/events
+#[utoipa::path(
+    get,
+    path = "/api/pending_beatmap/events",
+    params(
+        ("hashes" = Option<String>, Query, description = "Comma-separated .osu MD5 hashes to follow", example = "d41d8cd98f00b204e9800998ecf8427e"),
+        ("ids" = Option<String>, Query, description = "Comma-separated osu! beatmap ids to follow", example = "123456,654321"),
+        ("Last-Event-ID" = Option<String>, Header, description = "Resume: event ids continue after this one. The current state of every item is sent again on reconnect")
+    ),
+    responses(
+        (status = 200, description = "`text/event-stream`. Each event is named after the new state (`queued`, `processing`, `done`, `failed`, `unknown`) and carries the item status. Event ids are a sequence that increases within a stream. `end` is sent and the stream closes once no item is queued or processing, or after 30 minutes.", body = HashStatusDto, content_type = "text/event-stream"),
+        (status = 400, description = "No subscription or malformed hashes/ids", body = ApiResponse<ErrorBody>),
+        (status = 429, description = "Too many event streams open on the server, or more than 4 for this client (API key, or IP without a key)", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "PendingBeatmap"
+)]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    headers: HeaderMap,
+    extensions: Extensions,
+    Query(q): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let hashes = match split_list(q.hashes.as_deref()) {
+        list if list.is_empty() => Vec::new(),
+        list => parse_hashes("hashes", list)?,
+    };
+    let osu_ids = split_list(q.ids.as_deref())
+        .into_iter()
+        .map(|id| {
+            id.parse::<i32>()
+                .map_err(|_| ApiError::validation(format!("Invalid osu id `{}`", id)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if hashes.is_empty() && osu_ids.is_empty() {
+        return Err(ApiError::validation("Provide `hashes` and/or `ids`"));
+    }
+    if hashes.len() + osu_ids.len() > MAX_HASHES {
+        return Err(ApiError::validation(format!(
+            "At most {} items per subscription",
+            MAX_HASHES
+        )));
+    }
+
+    let client = client_key(&headers, &extensions);
+    let slot = StreamSlot::acquire(client.clone()).ok_or_else(|| {
+        tracing::warn!(client = %client, "pending beatmap event streams exhausted");
+        ApiError::RateLimited {
+            retry_after: STREAMS_FULL_RETRY_AFTER,
+        }
+    })?;
+
+    // La numérotation reprend après le dernier id reçu par le client
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(0);
+
+    let watch = Watch {
+        pool: db.get_pool().clone(),
+        hashes,
+        osu_ids,
+        last: HashMap::new(),
+        last_event_id,
+        deadline: Instant::now() + MAX_STREAM_LIFETIME,
+        started: false,
+        finished: false,
+        _slot: slot,
+    };
+
+    let stream = stream::unfold(watch, |mut watch| async move {
+        if watch.finished {
+            return None;
+        }
+        if watch.started {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        watch.started = true;
+
+        if Instant::now() >= watch.deadline {
+            watch.finished = true;
+            let end = Event::default()
+                .event("end")
+                .data("stream lifetime reached, reconnect to resume");
+            return Some((stream::iter(vec![Ok(end)]), watch));
+        }
+
+        let events = match watch.poll().await {
+            Ok(events) => events,
+            Err(err) => {
+                tracing::error!(error = %err, "failed to poll pending beatmap progress");
+                Vec::new()
+            }
+        };
+        Some((stream::iter(events), watch))
+    })
+    .flatten();
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn split_list(raw: Option<&str>) -> Vec<String> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Place d'un flux, parmi les `MAX_STREAMS` et celles de son client ; rendue avec le flux
+struct StreamSlot {
+    client: String,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl StreamSlot {
+    fn acquire(client: String) -> Option<Self> {
+        let mut open = CLIENT_STREAMS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let count = open.get(&client).copied().unwrap_or(0);
+        if count >= MAX_STREAMS_PER_CLIENT {
+            return None;
+        }
+        let permit = OPEN_STREAMS.clone().try_acquire_owned().ok()?;
+        open.insert(client.clone(), count + 1);
+        Some(Self {
+            client,
+            _permit: permit,
+        })
+    }
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        let mut open = CLIENT_STREAMS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(count) = open.get_mut(&self.client) {
+            *count -= 1;
+            if *count == 0 {
+                open.remove(&self.client);
+            }
+        }
+    }
+}
+
+/// État d'un abonnement : dernier statut envoyé pour chaque élément suivi
+struct Watch {
+    pool: PgPool,
+    hashes: Vec<String>,
+    osu_ids: Vec<i32>,
+    last: HashMap<String, HashStatusDto>,
+    /// Id du dernier événement envoyé (ou reçu par le client avant reconnexion)
+    last_event_id: u64,
+    deadline: Instant,
+    started: bool,
+    finished: bool,
+    _slot: StreamSlot,
+}
+
+impl Watch {
+    async fn current(&self) -> Result<Vec<(String, HashStatusDto)>, sqlx::Error> {
+        let mut current = Vec::with_capacity(self.hashes.len() + self.osu_ids.len());
+
+        if !self.hashes.is_empty() {
+            let mut found: HashMap<String, HashStatusDto> =
+                find_progress_by_hashes(&self.pool, &self.hashes)
+                    .await?
+                    .into_iter()
+                    .map(|row| (row.osu_hash.clone(), HashStatusDto::from(row)))
+                    .collect();
+            for hash in &self.hashes {
+                let status = found
+                    .remove(hash)
+                    .unwrap_or_else(|| HashStatusDto::unknown(hash.clone(), None));
+                current.push((format!("hash:{}", hash), status));
+            }
+        }
+
+        if !self.osu_ids.is_empty() {
+            let mut found: HashMap<i32, HashStatusDto> =
+                find_progress_by_osu_ids(&self.pool, &self.osu_ids)
+                    .await?
+                    .into_iter()
+                    .map(HashStatusDto::from)
+                    .filter_map(|status| status.beatmap_osu_id.map(|id| (id, status)))
+                    .collect();
+            for id in &self.osu_ids {
+                let status = found
+                    .remove(id)
+                    .unwrap_or_else(|| HashStatusDto::unknown(String::new(), Some(*id)));
+                current.push((format!("id:{}", id), status));
+            }
+        }
+
+        Ok(current)
+    }
+
+    /// Événements pour les éléments dont le statut a changé depuis le dernier envoi
+    async fn poll(&mut self) -> Result<Vec<Result<Event, axum::Error>>, sqlx::Error> {
+        let current = self.current().await?;
+
+        let mut changed: Vec<HashStatusDto> = Vec::new();
+        for (key, status) in current {
+            if self.last.get(&key) == Some(&status) {
+                continue;
+            }
+            changed.push(status.clone());
+            self.last.insert(key, status);
+        }
+        changed.sort_by_key(|status| status.updated_at);
+
+        let mut events: Vec<Result<Event, axum::Error>> = changed
+            .iter()
+            .map(|status| {
+                self.last_event_id += 1;
+                Event::default()
+                    .event(status.state.as_str())
+                    .id(self.last_event_id.to_string())
+                    .json_data(status)
+            })
+            .collect();
+
+        // Un élément inconnu ne changera pas tant qu'il n'est pas importé : seul
+        // ce qui est en file ou en cours de traitement garde le flux ouvert
+        self.finished = !self.last.values().any(|s| s.state.is_pending());
+        if self.finished {
+            events.push(Ok(Event::default()
+                .event("end")
+                .data("no item is queued or processing")));
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_are_capped_per_client() {
+        let slots: Vec<StreamSlot> = (0..MAX_STREAMS_PER_CLIENT)
+            .map(|_| StreamSlot::acquire("ip:192.0.2.1".to_string()).unwrap())
+            .collect();
+        assert!(StreamSlot::acquire("ip:192.0.2.1".to_string()).is_none());
+        // Les autres clients ne sont pas concernés
+        assert!(StreamSlot::acquire("ip:192.0.2.2".to_string()).is_some());
+
+        drop(slots);
+        assert!(StreamSlot::acquire("ip:192.0.2.1".to_string()).is_some());
+        assert!(!CLIENT_STREAMS.lock().unwrap().contains_key("ip:192.0.2.1"));
+    }
+}
@@ -1 +1,2 @@
+pub mod events;
 pub mod status_by_osu_id;
@@ -1,9 +1,12 @@
-use axum::{Json, extract::Path, extract::State, http::StatusCode};
+use axum::{Json, extract::State};
 use db::db::DatabaseManager;
 use dto::common::ApiResponse;
 use dto::models::pending_beatmap::status::query::by_osu_id::find_status_by_osu_id;
 use dto::models::pending_beatmap::status::types::PendingStatusDto;
 
+use crate::error::extract::Path;
+use crate::error::{ApiError, ErrorBody};
+
 #[utoipa::path(
     get,
     path = "/api/pending_beatmap/status/{id}",
@@ -14,22 +17,27 @@ use dto::models::pending_beatmap::status::types::PendingStatusDto;
     )),
     responses(
         (status = 200, description = "Status fetched", body = ApiResponse<PendingStatusDto>),
-        (status = 404, description = "Not found"),
-        (status = 500, description = "Internal server error")
+        (status = 400, description = "Invalid id", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorBody>)
     ),
     tag = "PendingBeatmap"
 )]
 pub async fn handler(
     State(db): State<DatabaseManager>,
     Path(id): Path<i32>,
-) -> Result<Json<ApiResponse<PendingStatusDto>>, StatusCode> {
+) -> Result<Json<ApiResponse<PendingStatusDto>>, ApiError> {
     let pool = db.get_pool();
 
-    let Some(status) = find_status_by_osu_id(pool, id)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    let Some(status) = find_status_by_osu_id(pool, id).await.map_err(|err| {
+        tracing::error!(error = %err, "failed to fetch pending status for osu_id {}", id);
+        ApiError::Internal
+    })?
     else {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found(format!(
+            "No pending beatmap with osu_id {}",
+            id
+        )));
     };
 
     Ok(Json(ApiResponse::ok("ok", Some(status))))
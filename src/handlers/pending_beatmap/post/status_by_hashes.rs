@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody, FieldError, extract};
+use crate::handlers::beatmapsets::batch::checksums::is_valid_md5;
+use crate::models::pending_beatmap::query::find_progress_by_hashes;
+use crate::models::pending_beatmap::types::{HashStatusDto, StatusBatchRequestDto};
+
+/// Nombre maximum de hashes par requête
+pub const MAX_HASHES: usize = 500;
+
+/// Normalise et valide une liste de hashes MD5, en signalant chaque entrée fautive
+pub fn parse_hashes(field: &str, hashes: Vec<String>) -> Result<Vec<String>, ApiError> {
+    if hashes.is_empty() {
+        return Err(ApiError::validation(format!(
+            "`{}` must not be empty",
+            field
+        )));
+    }
+    if hashes.len() > MAX_HASHES {
+        return Err(ApiError::validation(format!(
+            "At most {} hashes per request",
+            MAX_HASHES
+        )));
+    }
+
+    let hashes: Vec<String> = hashes
+        .into_iter()
+        .map(|h| h.trim().to_ascii_lowercase())
+        .collect();
+    let invalid: Vec<FieldError> = hashes
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !is_valid_md5(h))
+        .map(|(i, _)| {
+            FieldError::new(
+                format!("{}[{}]", field, i),
+                "expected a 32-character hex MD5",
+            )
+        })
+        .collect();
+    if !invalid.is_empty() {
+        return Err(ApiError::invalid_fields(invalid));
+    }
+
+    Ok(hashes)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/pending_beatmap/status",
+    request_body = StatusBatchRequestDto,
+    responses(
+        (status = 200, description = "Status of each hash, in request order", body = ApiResponse<Vec<HashStatusDto>>),
+        (status = 400, description = "Empty, oversized or malformed hash list", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal server error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "PendingBeatmap"
+)]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    extract::Json(payload): extract::Json<StatusBatchRequestDto>,
+) -> Result<Json<ApiResponse<Vec<HashStatusDto>>>, ApiError> {
+    let hashes = parse_hashes("hashes", payload.hashes)?;
+
+    let found: HashMap<String, HashStatusDto> = find_progress_by_hashes(db.get_pool(), &hashes)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to fetch pending status by hashes");
+            ApiError::Internal
+        })?
+        .into_iter()
+        .map(|row| (row.osu_hash.clone(), HashStatusDto::from(row)))
+        .collect();
+
+    let statuses = hashes
+        .into_iter()
+        .map(|h| match found.get(&h) {
+            Some(status) => status.clone(),
+            None => HashStatusDto::unknown(h, None),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::ok("ok", Some(statuses))))
+}
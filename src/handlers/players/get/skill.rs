@@ -0,0 +1,91 @@
+use axum::{Json, extract::State};
+use chrono::Utc;
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::beatmapset::query::DEFAULT_RATING_TYPE;
+use crate::models::beatmapset::types::RATING_TYPES;
+use crate::models::player::query::find_recent_plays;
+use crate::models::player::query::profile_plays;
+use crate::models::player::skill::{MAX_PLAYS, estimate};
+use crate::models::player::types::PlayerSkillDto;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SkillQuery {
+    /// Rating type of the overall level (default overall)
+    #[param(example = "overall")]
+    pub rating_type: Option<String>,
+}
+
+/// GET /api/players/{player}/skill
+#[utoipa::path(
+    get,
+    path = "/api/players/{player}/skill",
+    params(
+        ("player" = String, Path, description = "Player name", example = "cookiezi"),
+        SkillQuery
+    ),
+    responses(
+        (status = 200, description = "Overall and per-skillset level estimated from the player's 200 most recent stored scores", body = ApiResponse<PlayerSkillDto>),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "No stored score for this player", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Players"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(player): Path<String>,
+    Query(q): Query<SkillQuery>,
+) -> Result<Json<ApiResponse<PlayerSkillDto>>, ApiError> {
+    if let Some(rating_type) = &q.rating_type
+        && !RATING_TYPES.contains(&rating_type.as_str())
+    {
+        return Err(ApiError::invalid_fields(vec![FieldError::new(
+            "rating_type",
+            format!(
+                "unknown value `{}`; allowed: {}",
+                rating_type,
+                RATING_TYPES.join(", ")
+            ),
+        )]));
+    }
+    let rating_type = q.rating_type.as_deref().unwrap_or(DEFAULT_RATING_TYPE);
+    let player = player.trim();
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to estimate skill of {}", player);
+        ApiError::Internal
+    };
+
+    let plays = find_recent_plays(pool, player, MAX_PLAYS as i64)
+        .await
+        .map_err(internal)?;
+    if plays.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "No score found for player {}",
+            player
+        )));
+    }
+    let submitted = plays.len();
+    let plays = profile_plays(pool, plays, rating_type)
+        .await
+        .map_err(internal)?;
+
+    Ok(Json(ApiResponse::ok(
+        "ok",
+        Some(estimate(
+            &plays,
+            submitted,
+            rating_type,
+            Utc::now().naive_utc(),
+        )),
+    )))
+}
@@ -0,0 +1,92 @@
+use axum::{Json, extract::State};
+use chrono::Utc;
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody, FieldError, extract};
+use crate::models::beatmapset::query::DEFAULT_RATING_TYPE;
+use crate::models::beatmapset::types::CENTIRATE_RANGE;
+use crate::models::beatmapset::types::RATING_TYPES;
+use crate::models::player::query::profile_plays;
+use crate::models::player::skill::{MAX_PLAYS, estimate};
+use crate::models::player::types::{PlayerSkillDto, SkillRequestDto};
+
+/// POST /api/players/skill
+#[utoipa::path(
+    post,
+    path = "/api/players/skill",
+    request_body = SkillRequestDto,
+    responses(
+        (status = 200, description = "Overall and per-skillset level estimated from the given plays", body = ApiResponse<PlayerSkillDto>),
+        (status = 400, description = "Empty, oversized or invalid play list", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Players"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    extract::Json(payload): extract::Json<SkillRequestDto>,
+) -> Result<Json<ApiResponse<PlayerSkillDto>>, ApiError> {
+    let now = Utc::now().naive_utc();
+    let mut errors = Vec::new();
+    if payload.plays.is_empty() || payload.plays.len() > MAX_PLAYS {
+        errors.push(FieldError::new(
+            "plays",
+            format!("must contain between 1 and {} plays", MAX_PLAYS),
+        ));
+    }
+    if let Some(rating_type) = &payload.rating_type
+        && !RATING_TYPES.contains(&rating_type.as_str())
+    {
+        errors.push(FieldError::new(
+            "rating_type",
+            format!(
+                "unknown value `{}`; allowed: {}",
+                rating_type,
+                RATING_TYPES.join(", ")
+            ),
+        ));
+    }
+    let (lowest, highest) = CENTIRATE_RANGE;
+    for (i, play) in payload.plays.iter().enumerate() {
+        if !(lowest..=highest).contains(&play.centirate) {
+            errors.push(FieldError::new(
+                format!("plays[{}].centirate", i),
+                format!("must be between {} and {}", lowest, highest),
+            ));
+        }
+        if !(0.0..=100.0).contains(&play.accuracy) {
+            errors.push(FieldError::new(
+                format!("plays[{}].accuracy", i),
+                "must be between 0 and 100",
+            ));
+        }
+        if play.played_at.is_some_and(|at| at > now) {
+            errors.push(FieldError::new(
+                format!("plays[{}].played_at", i),
+                "must not be in the future",
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let rating_type = payload
+        .rating_type
+        .as_deref()
+        .unwrap_or(DEFAULT_RATING_TYPE);
+    let submitted = payload.plays.len();
+    let plays = profile_plays(db.get_pool(), payload.plays, rating_type)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to fetch rates of submitted plays");
+            ApiError::Internal
+        })?;
+
+    Ok(Json(ApiResponse::ok(
+        "ok",
+        Some(estimate(&plays, submitted, rating_type, now)),
+    )))
+}
@@ -0,0 +1,244 @@
+use axum::{Json, extract::State};
+use chrono::Utc;
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::Query;
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::handlers::beatmapsets::filters::{BeatmapFilters, FilterParams};
+use crate::models::beatmapset::types::PATTERN_TYPES;
+use crate::models::player::query::find_recent_plays;
+use crate::models::player::query::profile_plays;
+use crate::models::player::skill::{MAX_PLAYS, estimate};
+use crate::models::player::types::SkillsetLevelDto;
+use crate::models::recommendation::query::find_recommendations;
+use crate::models::recommendation::types::{RecommendationDto, RecommendationsDto, SkillsetTarget};
+
+const DEFAULT_LIMIT: i64 = 10;
+const MAX_LIMIT: i64 = 50;
+/// Skillsets faibles ciblés sans `weak`
+const DEFAULT_WEAK: usize = 2;
+
+/// Fenêtre visée, en multiple du niveau du joueur sur le skillset
+const TARGET_MIN: f64 = 1.02;
+const TARGET_IDEAL: f64 = 1.05;
+const TARGET_MAX: f64 = 1.10;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RecommendationQuery {
+    /// Player whose level is computed from stored scores; their played maps are excluded
+    #[param(example = "cookiezi")]
+    pub player: Option<String>,
+    /// Explicit stream level (overrides the computed one)
+    #[serde(alias = "level[stream]", alias = "level.stream")]
+    #[param(rename = "level[stream]", example = 21.5)]
+    pub level_stream: Option<f64>,
+    /// Explicit jumpstream level
+    #[serde(alias = "level[jumpstream]", alias = "level.jumpstream")]
+    #[param(rename = "level[jumpstream]", example = 22.0)]
+    pub level_jumpstream: Option<f64>,
+    /// Explicit handstream level
+    #[serde(alias = "level[handstream]", alias = "level.handstream")]
+    #[param(rename = "level[handstream]", example = 20.0)]
+    pub level_handstream: Option<f64>,
+    /// Explicit stamina level
+    #[serde(alias = "level[stamina]", alias = "level.stamina")]
+    #[param(rename = "level[stamina]", example = 21.0)]
+    pub level_stamina: Option<f64>,
+    /// Explicit jackspeed level
+    #[serde(alias = "level[jackspeed]", alias = "level.jackspeed")]
+    #[param(rename = "level[jackspeed]", example = 18.0)]
+    pub level_jackspeed: Option<f64>,
+    /// Explicit chordjack level
+    #[serde(alias = "level[chordjack]", alias = "level.chordjack")]
+    #[param(rename = "level[chordjack]", example = 19.5)]
+    pub level_chordjack: Option<f64>,
+    /// Explicit technical level
+    #[serde(alias = "level[technical]", alias = "level.technical")]
+    #[param(rename = "level[technical]", example = 20.5)]
+    pub level_technical: Option<f64>,
+    /// Number of weakest skillsets to target (default 2)
+    #[param(example = 2)]
+    pub weak: Option<usize>,
+    /// Number of recommendations (default 10, max 50)
+    #[param(example = 10)]
+    pub limit: Option<i64>,
+}
+
+impl RecommendationQuery {
+    /// Niveaux fournis, dans l'ordre de `PATTERN_TYPES`
+    fn explicit_levels(&self) -> [(&'static str, Option<f64>); 7] {
+        [
+            ("stream", self.level_stream),
+            ("jumpstream", self.level_jumpstream),
+            ("handstream", self.level_handstream),
+            ("stamina", self.level_stamina),
+            ("jackspeed", self.level_jackspeed),
+            ("chordjack", self.level_chordjack),
+            ("technical", self.level_technical),
+        ]
+    }
+
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        for (pattern_type, level) in self.explicit_levels() {
+            if level.is_some_and(|v| !v.is_finite() || v <= 0.0) {
+                errors.push(FieldError::new(
+                    format!("level[{}]", pattern_type),
+                    "must be greater than 0",
+                ));
+            }
+        }
+        if self
+            .weak
+            .is_some_and(|v| !(1..=PATTERN_TYPES.len()).contains(&v))
+        {
+            errors.push(FieldError::new(
+                "weak",
+                format!("must be between 1 and {}", PATTERN_TYPES.len()),
+            ));
+        }
+        if self.limit.is_some_and(|v| !(1..=MAX_LIMIT).contains(&v)) {
+            errors.push(FieldError::new(
+                "limit",
+                format!("must be between 1 and {}", MAX_LIMIT),
+            ));
+        }
+        if self.player.as_deref().is_some_and(|p| p.trim().is_empty()) {
+            errors.push(FieldError::new("player", "must not be empty"));
+        }
+        errors
+    }
+}
+
+/// GET /api/recommendations
+#[utoipa::path(
+    get,
+    path = "/api/recommendations",
+    params(RecommendationQuery, FilterParams),
+    responses(
+        (status = 200, description = "Beatmaps and rates slightly above the player's level in their weakest skillsets, each with the reason it was picked", body = ApiResponse<RecommendationsDto>),
+        (status = 400, description = "Invalid parameters, or neither `player` nor any `level[...]` given", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "No stored score for this player", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Players"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    BeatmapFilters(filters): BeatmapFilters,
+    Query(q): Query<RecommendationQuery>,
+) -> Result<Json<ApiResponse<RecommendationsDto>>, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+    let player = q.player.as_deref().map(str::trim);
+    let rating_type = filters.primary_rating_type();
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to compute recommendations");
+        ApiError::Internal
+    };
+
+    // Niveaux calculés depuis les scores, puis remplacés par ceux fournis
+    let mut profile: Vec<SkillsetLevelDto> = Vec::new();
+    let explicit = q.explicit_levels();
+    if let Some(player) = player
+        && explicit.iter().any(|(_, level)| level.is_none())
+    {
+        let plays = find_recent_plays(pool, player, MAX_PLAYS as i64)
+            .await
+            .map_err(internal)?;
+        if plays.is_empty() && explicit.iter().all(|(_, level)| level.is_none()) {
+            return Err(ApiError::not_found(format!(
+                "No score found for player {}",
+                player
+            )));
+        }
+        let submitted = plays.len();
+        let plays = profile_plays(pool, plays, rating_type)
+            .await
+            .map_err(internal)?;
+        profile = estimate(&plays, submitted, rating_type, Utc::now().naive_utc()).skillsets;
+    }
+    for (pattern_type, level) in explicit {
+        let Some(value) = level else { continue };
+        match profile.iter_mut().find(|s| s.pattern_type == pattern_type) {
+            Some(existing) => existing.value = value,
+            None => profile.push(SkillsetLevelDto {
+                pattern_type: pattern_type.to_string(),
+                value,
+            }),
+        }
+    }
+    if profile.is_empty() {
+        return Err(ApiError::validation(
+            "Provide `player` with stored scores, or at least one `level[<skillset>]`",
+        ));
+    }
+
+    let mut weakest = profile.clone();
+    weakest.sort_by(|a, b| a.value.total_cmp(&b.value));
+    weakest.truncate(q.weak.unwrap_or(DEFAULT_WEAK));
+    let targets: Vec<SkillsetTarget> = weakest
+        .iter()
+        .map(|s| SkillsetTarget {
+            pattern_type: s.pattern_type.clone(),
+            level: s.value,
+            min: s.value * TARGET_MIN,
+            max: s.value * TARGET_MAX,
+            ideal: s.value * TARGET_IDEAL,
+        })
+        .collect();
+
+    // Même part pour chaque skillset ciblé, sans proposer deux fois la même difficulté
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT);
+    let per_target = (limit + targets.len() as i64 - 1) / targets.len() as i64;
+    let mut recommendations: Vec<RecommendationDto> = Vec::new();
+    let mut picked: Vec<i32> = Vec::new();
+    for target in &targets {
+        let rows = find_recommendations(
+            pool,
+            &filters,
+            target,
+            rating_type,
+            player,
+            &picked,
+            per_target,
+        )
+        .await
+        .map_err(internal)?;
+        for row in rows {
+            picked.push(row.beatmap.id);
+            recommendations.push(RecommendationDto::new(row, target));
+        }
+    }
+    recommendations.truncate(limit as usize);
+
+    Ok(Json(ApiResponse::ok(
+        "ok",
+        Some(RecommendationsDto {
+            profile,
+            weak_skillsets: targets.into_iter().map(|t| t.pattern_type).collect(),
+            recommendations,
+        }),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_levels_follow_pattern_types() {
+        let q: RecommendationQuery = serde_urlencoded::from_str("").unwrap();
+        let names: Vec<&str> = q.explicit_levels().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, PATTERN_TYPES);
+    }
+}
@@ -0,0 +1,103 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::beatmapset::query::DEFAULT_RATING_TYPE;
+use crate::models::beatmapset::types::RATING_TYPES;
+use crate::models::score::query::find_best_scores;
+use crate::models::score::types::ScoreDto;
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BestScoresQuery {
+    /// Rating type used to rank scores (default overall)
+    #[param(example = "overall")]
+    pub rating_type: Option<String>,
+    /// Ignore scores below this accuracy
+    #[param(example = 93.0)]
+    pub min_accuracy: Option<f64>,
+    /// Number of scores (default 50, max 200)
+    #[param(example = 50)]
+    pub limit: Option<i64>,
+}
+
+impl BestScoresQuery {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if let Some(rating_type) = &self.rating_type
+            && !RATING_TYPES.contains(&rating_type.as_str())
+        {
+            errors.push(FieldError::new(
+                "rating_type",
+                format!(
+                    "unknown value `{}`; allowed: {}",
+                    rating_type,
+                    RATING_TYPES.join(", ")
+                ),
+            ));
+        }
+        if self
+            .min_accuracy
+            .is_some_and(|a| !(0.0..=100.0).contains(&a))
+        {
+            errors.push(FieldError::new("min_accuracy", "must be between 0 and 100"));
+        }
+        if self.limit.is_some_and(|v| !(1..=MAX_LIMIT).contains(&v)) {
+            errors.push(FieldError::new(
+                "limit",
+                format!("must be between 1 and {}", MAX_LIMIT),
+            ));
+        }
+        errors
+    }
+}
+
+/// GET /api/players/{player}/scores/best
+#[utoipa::path(
+    get,
+    path = "/api/players/{player}/scores/best",
+    params(
+        ("player" = String, Path, description = "Player name", example = "cookiezi"),
+        BestScoresQuery
+    ),
+    responses(
+        (status = 200, description = "Best score per beatmap (highest rated rate, then accuracy), hardest first", body = ApiResponse<Vec<ScoreDto>>),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Scores"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(player): Path<String>,
+    Query(q): Query<BestScoresQuery>,
+) -> Result<Json<ApiResponse<Vec<ScoreDto>>>, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let rating_type = q.rating_type.as_deref().unwrap_or(DEFAULT_RATING_TYPE);
+    let scores = find_best_scores(
+        db.get_pool(),
+        player.trim(),
+        rating_type,
+        q.min_accuracy,
+        q.limit.unwrap_or(DEFAULT_LIMIT),
+    )
+    .await
+    .map_err(|err| {
+        tracing::error!(error = %err, "failed to fetch best scores of {}", player);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(scores))))
+}
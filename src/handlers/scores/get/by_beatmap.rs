@@ -0,0 +1,100 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::rate::query::find_beatmap_id;
+use crate::models::score::query::find_beatmap_scores;
+use crate::models::score::types::ScoreDto;
+
+const DEFAULT_PER_PAGE: i64 = 50;
+const MAX_PER_PAGE: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BeatmapScoresQuery {
+    /// Only scores played at this rate
+    #[param(example = 100)]
+    pub centirate: Option<i32>,
+    /// Page number, starting at 0
+    #[param(example = 0)]
+    pub page: Option<i64>,
+    /// Scores per page (default 50, max 100)
+    #[param(example = 50)]
+    pub per_page: Option<i64>,
+}
+
+impl BeatmapScoresQuery {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.page.is_some_and(|p| p < 0) {
+            errors.push(FieldError::new(
+                "page",
+                "must be greater than or equal to 0",
+            ));
+        }
+        if self
+            .per_page
+            .is_some_and(|p| !(1..=MAX_PER_PAGE).contains(&p))
+        {
+            errors.push(FieldError::new(
+                "per_page",
+                format!("must be between 1 and {}", MAX_PER_PAGE),
+            ));
+        }
+        errors
+    }
+}
+
+/// GET /api/beatmaps/{beatmap_osu_id}/scores
+#[utoipa::path(
+    get,
+    path = "/api/beatmaps/{beatmap_osu_id}/scores",
+    params(
+        ("beatmap_osu_id" = i32, Path, description = "Beatmap osu ID", example = 123456),
+        BeatmapScoresQuery
+    ),
+    responses(
+        (status = 200, description = "Scores on the beatmap (or one of its rates), best accuracy first", body = ApiResponse<Vec<ScoreDto>>),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Beatmap not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Scores"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(beatmap_osu_id): Path<i32>,
+    Query(q): Query<BeatmapScoresQuery>,
+) -> Result<Json<ApiResponse<Vec<ScoreDto>>>, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch scores of beatmap {}", beatmap_osu_id);
+        ApiError::Internal
+    };
+
+    let beatmap_id = find_beatmap_id(pool, beatmap_osu_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Beatmap {} not found", beatmap_osu_id)))?;
+
+    let per_page = q.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    let offset =
+        q.page.unwrap_or(0).checked_mul(per_page).ok_or_else(|| {
+            ApiError::invalid_fields(vec![FieldError::new("page", "is too large")])
+        })?;
+    let scores = find_beatmap_scores(pool, beatmap_id, q.centirate, per_page, offset)
+        .await
+        .map_err(internal)?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(scores))))
+}
@@ -0,0 +1,2 @@
+pub mod best_by_player;
+pub mod by_beatmap;
@@ -0,0 +1,144 @@
+use axum::{Json, extract::State};
+use chrono::Utc;
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody, FieldError, extract};
+use crate::models::beatmapset::types::CENTIRATE_RANGE;
+use crate::models::rate::query::find_beatmap_id;
+use crate::models::score::query::{find_score_rate, insert_score};
+use crate::models::score::types::{
+    ACCURACY_TOLERANCE, MAX_PLAYER_LEN, NewScore, ScoreDto, ScoreSubmitDto,
+};
+
+/// POST /api/scores
+#[utoipa::path(
+    post,
+    path = "/api/scores",
+    request_body = ScoreSubmitDto,
+    responses(
+        (status = 200, description = "Stored score", body = ApiResponse<ScoreDto>),
+        (status = 400, description = "Invalid score: `accuracy` not matching the judgements, `max_combo` above the judgement count, or a judgement count differing from the rate's note count", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Beatmap or rate not found", body = ApiResponse<ErrorBody>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `import` scope", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Scores"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    extract::Json(payload): extract::Json<ScoreSubmitDto>,
+) -> Result<Json<ApiResponse<ScoreDto>>, ApiError> {
+    let now = Utc::now().naive_utc();
+    let player = payload.player.trim();
+    let judgements = payload.judgements;
+
+    let mut errors = Vec::new();
+    if player.is_empty() || player.chars().count() > MAX_PLAYER_LEN {
+        errors.push(FieldError::new(
+            "player",
+            format!("must be between 1 and {} characters", MAX_PLAYER_LEN),
+        ));
+    }
+    let (lowest, highest) = CENTIRATE_RANGE;
+    if !(lowest..=highest).contains(&payload.centirate) {
+        errors.push(FieldError::new(
+            "centirate",
+            format!("must be between {} and {}", lowest, highest),
+        ));
+    }
+    for (field, count) in [
+        ("judgements.perfect", judgements.perfect),
+        ("judgements.great", judgements.great),
+        ("judgements.good", judgements.good),
+        ("judgements.ok", judgements.ok),
+        ("judgements.meh", judgements.meh),
+        ("judgements.miss", judgements.miss),
+        ("max_combo", payload.max_combo),
+    ] {
+        if count < 0 {
+            errors.push(FieldError::new(field, "must be greater than or equal to 0"));
+        }
+    }
+    if judgements.total() <= 0 {
+        errors.push(FieldError::new(
+            "judgements",
+            "must contain at least one judgement",
+        ));
+    }
+    if payload.max_combo as i64 > judgements.total() {
+        errors.push(FieldError::new(
+            "max_combo",
+            "must not exceed the number of judgements",
+        ));
+    }
+    let accuracy = judgements.accuracy().unwrap_or_default();
+    if payload
+        .accuracy
+        .is_some_and(|a| !a.is_finite() || (a - accuracy).abs() > ACCURACY_TOLERANCE)
+    {
+        errors.push(FieldError::new(
+            "accuracy",
+            format!("does not match the judgements ({:.2})", accuracy),
+        ));
+    }
+    if payload.played_at.is_some_and(|at| at > now) {
+        errors.push(FieldError::new("played_at", "must not be in the future"));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(
+            error = %err,
+            "failed to store score on beatmap {} at rate {}",
+            payload.beatmap_osu_id,
+            payload.centirate
+        );
+        ApiError::Internal
+    };
+
+    let beatmap_id = find_beatmap_id(pool, payload.beatmap_osu_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Beatmap {} not found", payload.beatmap_osu_id))
+        })?;
+    let rate = find_score_rate(pool, beatmap_id, payload.centirate)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!(
+                "No rate {} for beatmap {}",
+                payload.centirate, payload.beatmap_osu_id
+            ))
+        })?;
+    if let Some(note_count) = rate.note_count
+        && judgements.total() != note_count as i64
+    {
+        return Err(ApiError::invalid_fields(vec![FieldError::new(
+            "judgements",
+            format!(
+                "total {} does not match the {} notes of the beatmap",
+                judgements.total(),
+                note_count
+            ),
+        )]));
+    }
+
+    let score = NewScore {
+        rates_id: rate.id,
+        player: player.to_string(),
+        accuracy,
+        judgements,
+        max_combo: payload.max_combo,
+        played_at: payload.played_at.unwrap_or(now),
+    };
+    let stored = insert_score(pool, &score).await.map_err(internal)?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(stored))))
+}
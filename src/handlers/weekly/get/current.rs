@@ -0,0 +1,74 @@
+use axum::{Json, extract::State};
+use chrono::{NaiveDateTime, Utc};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use sqlx::PgPool;
+
+use crate::error::{ApiError, ErrorBody};
+use crate::models::beatmapset::types::ListFilters;
+use crate::models::weekly::query::{
+    attach_weekly_beatmaps, find_current_weekly, insert_weekly, select_weekly_beatmaps,
+};
+use crate::models::weekly::types::{
+    DEFAULT_WEEKLY_COUNT, WeeklyDto, WeeklyRow, default_seed, week_bounds,
+};
+
+/// Crée la semaine en cours avec le tirage par défaut : graine de la semaine,
+/// sans filtre. `None` si aucune map n'est disponible.
+async fn create_current_weekly(
+    pool: &PgPool,
+    now: NaiveDateTime,
+) -> Result<Option<WeeklyRow>, sqlx::Error> {
+    let day = now.date();
+    let (starts_at, ends_at) = week_bounds(day);
+    let seed = default_seed(day);
+
+    let beatmaps =
+        select_weekly_beatmaps(pool, &ListFilters::default(), seed, DEFAULT_WEEKLY_COUNT).await?;
+    if beatmaps.is_empty() {
+        return Ok(None);
+    }
+
+    match insert_weekly(pool, starts_at, ends_at, seed, "", &beatmaps).await? {
+        Some(weekly) => Ok(Some(weekly)),
+        // Créée entre-temps par une autre requête ou par un admin
+        None => find_current_weekly(pool, now).await,
+    }
+}
+
+/// GET /api/weekly/current
+#[utoipa::path(
+    get,
+    path = "/api/weekly/current",
+    responses(
+        (status = 200, description = "Weekly challenge running now, with its maps. If no admin created it with `POST /api/weekly`, the first call creates it with the default draw: the week's seed and no filters", body = ApiResponse<WeeklyDto>),
+        (status = 404, description = "No weekly challenge this week, and no beatmap to draw one from", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Weekly"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+) -> Result<Json<ApiResponse<WeeklyDto>>, ApiError> {
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch current weekly");
+        ApiError::Internal
+    };
+
+    let now = Utc::now().naive_utc();
+    let weekly = match find_current_weekly(pool, now).await.map_err(internal)? {
+        Some(weekly) => Some(weekly),
+        None => create_current_weekly(pool, now).await.map_err(internal)?,
+    }
+    .ok_or_else(|| {
+        ApiError::not_found("No weekly challenge this week, and no beatmap to draw one from")
+    })?;
+    let weekly = attach_weekly_beatmaps(pool, vec![weekly])
+        .await
+        .map_err(internal)?
+        .pop();
+
+    Ok(Json(ApiResponse::ok("ok", weekly)))
+}
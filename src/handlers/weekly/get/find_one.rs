@@ -0,0 +1,43 @@
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::extract::Path;
+use crate::error::{ApiError, ErrorBody};
+use crate::models::weekly::query::{attach_weekly_beatmaps, find_weekly};
+use crate::models::weekly::types::WeeklyDto;
+
+/// GET /api/weekly/{id}
+#[utoipa::path(
+    get,
+    path = "/api/weekly/{id}",
+    params(("id" = i32, Path, description = "Weekly ID", example = 12)),
+    responses(
+        (status = 200, description = "Weekly challenge with its maps, seed and filters", body = ApiResponse<WeeklyDto>),
+        (status = 404, description = "Weekly not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Weekly"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<WeeklyDto>>, ApiError> {
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch weekly {}", id);
+        ApiError::Internal
+    };
+
+    let weekly = find_weekly(pool, id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Weekly {} not found", id)))?;
+    let weekly = attach_weekly_beatmaps(pool, vec![weekly])
+        .await
+        .map_err(internal)?
+        .pop();
+
+    Ok(Json(ApiResponse::ok("ok", weekly)))
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use axum::{Json, extract::State};
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::{Path, Query};
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::weekly::query::{
+    find_map_scores, find_standings, find_weekly, find_weekly_beatmaps,
+};
+use crate::models::weekly::types::{MapLeaderboardDto, MapScoreDto, WeeklyLeaderboardDto};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LeaderboardQuery {
+    /// Entries per ranking (default 50, max 200)
+    #[param(example = 50)]
+    pub limit: Option<i64>,
+}
+
+/// GET /api/weekly/{id}/leaderboard
+#[utoipa::path(
+    get,
+    path = "/api/weekly/{id}/leaderboard",
+    params(
+        ("id" = i32, Path, description = "Weekly ID", example = 12),
+        LeaderboardQuery
+    ),
+    responses(
+        (status = 200, description = "Overall standings (sum of best accuracies) and per-map rankings, built from scores submitted to `POST /api/scores` on the week's maps, at the week's rates, played during the week", body = ApiResponse<WeeklyLeaderboardDto>),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 404, description = "Weekly not found", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Weekly"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Path(id): Path<i32>,
+    Query(q): Query<LeaderboardQuery>,
+) -> Result<Json<ApiResponse<WeeklyLeaderboardDto>>, ApiError> {
+    if q.limit.is_some_and(|v| !(1..=MAX_LIMIT).contains(&v)) {
+        return Err(ApiError::invalid_fields(vec![FieldError::new(
+            "limit",
+            format!("must be between 1 and {}", MAX_LIMIT),
+        )]));
+    }
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT);
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to fetch leaderboard of weekly {}", id);
+        ApiError::Internal
+    };
+
+    find_weekly(pool, id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| ApiError::not_found(format!("Weekly {} not found", id)))?;
+
+    let standings = find_standings(pool, id, limit).await.map_err(internal)?;
+
+    let mut scores_by_beatmap: HashMap<i32, Vec<MapScoreDto>> = HashMap::new();
+    for score in find_map_scores(pool, id, limit).await.map_err(internal)? {
+        scores_by_beatmap
+            .entry(score.beatmap_id)
+            .or_default()
+            .push(score);
+    }
+    let beatmaps = find_weekly_beatmaps(pool, &[id])
+        .await
+        .map_err(internal)?
+        .into_iter()
+        .map(|row| MapLeaderboardDto {
+            position: row.position,
+            centirate: row.centirate,
+            beatmap_osu_id: row.beatmap.osu_id,
+            scores: scores_by_beatmap
+                .remove(&row.beatmap.id)
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::ok(
+        "ok",
+        Some(WeeklyLeaderboardDto {
+            weekly_id: id,
+            standings,
+            beatmaps,
+        }),
+    )))
+}
@@ -0,0 +1,90 @@
+use axum::{Json, extract::State};
+use chrono::Utc;
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::error::extract::Query;
+use crate::error::{ApiError, ErrorBody, FieldError};
+use crate::models::weekly::query::{attach_weekly_beatmaps, find_past_weeklies};
+use crate::models::weekly::types::WeeklyDto;
+
+const DEFAULT_PER_PAGE: i64 = 10;
+const MAX_PER_PAGE: i64 = 50;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WeeklyListQuery {
+    /// Page number, starting at 0
+    #[param(example = 0)]
+    pub page: Option<i64>,
+    /// Weeks per page (default 10, max 50)
+    #[param(example = 10)]
+    pub per_page: Option<i64>,
+}
+
+impl WeeklyListQuery {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.page.is_some_and(|p| p < 0) {
+            errors.push(FieldError::new(
+                "page",
+                "must be greater than or equal to 0",
+            ));
+        }
+        if self
+            .per_page
+            .is_some_and(|p| !(1..=MAX_PER_PAGE).contains(&p))
+        {
+            errors.push(FieldError::new(
+                "per_page",
+                format!("must be between 1 and {}", MAX_PER_PAGE),
+            ));
+        }
+        errors
+    }
+}
+
+/// GET /api/weekly
+#[utoipa::path(
+    get,
+    path = "/api/weekly",
+    params(WeeklyListQuery),
+    responses(
+        (status = 200, description = "Finished weekly challenges, most recent first", body = ApiResponse<Vec<WeeklyDto>>),
+        (status = 400, description = "Invalid parameters", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Weekly"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    Query(q): Query<WeeklyListQuery>,
+) -> Result<Json<ApiResponse<Vec<WeeklyDto>>>, ApiError> {
+    let errors = q.validate();
+    if !errors.is_empty() {
+        return Err(ApiError::invalid_fields(errors));
+    }
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to list past weeklies");
+        ApiError::Internal
+    };
+
+    let per_page = q.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    let offset =
+        q.page.unwrap_or(0).checked_mul(per_page).ok_or_else(|| {
+            ApiError::invalid_fields(vec![FieldError::new("page", "is too large")])
+        })?;
+    let weeklies = find_past_weeklies(pool, Utc::now().naive_utc(), per_page, offset)
+        .await
+        .map_err(internal)?;
+    let weeklies = attach_weekly_beatmaps(pool, weeklies)
+        .await
+        .map_err(internal)?;
+
+    Ok(Json(ApiResponse::ok("ok", Some(weeklies))))
+}
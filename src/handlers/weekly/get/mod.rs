@@ -0,0 +1,4 @@
+pub mod current;
+pub mod find_one;
+pub mod leaderboard;
+pub mod list;
@@ -0,0 +1,93 @@
+use axum::{
+    Json,
+    extract::{RawQuery, State},
+};
+use chrono::Utc;
+use db::db::DatabaseManager;
+use dto::common::ApiResponse;
+
+use crate::error::{ApiError, ErrorBody, FieldError, extract};
+use crate::handlers::beatmapsets::filters::{BeatmapFilters, FilterParams};
+use crate::models::weekly::query::{attach_weekly_beatmaps, insert_weekly, select_weekly_beatmaps};
+use crate::models::weekly::types::{
+    DEFAULT_WEEKLY_COUNT, MAX_WEEKLY_COUNT, WeeklyCreateDto, WeeklyDto, default_seed, week_bounds,
+};
+
+/// POST /api/weekly
+#[utoipa::path(
+    post,
+    path = "/api/weekly",
+    params(FilterParams),
+    request_body = WeeklyCreateDto,
+    responses(
+        (status = 200, description = "Created weekly challenge (or its preview with `dry_run`). Without it, `GET /api/weekly/current` creates the current week with the default draw", body = ApiResponse<WeeklyDto>),
+        (status = 400, description = "Invalid parameters, or no beatmap matches the filters", body = ApiResponse<ErrorBody>),
+        (status = 409, description = "A weekly challenge already exists for that week", body = ApiResponse<ErrorBody>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<ErrorBody>),
+        (status = 403, description = "API key lacks the `admin` scope", body = ApiResponse<ErrorBody>),
+        (status = 500, description = "Internal error", body = ApiResponse<ErrorBody>)
+    ),
+    tag = "Weekly"
+)]
+#[axum::debug_handler]
+pub async fn handler(
+    State(db): State<DatabaseManager>,
+    BeatmapFilters(filters): BeatmapFilters,
+    RawQuery(raw_filters): RawQuery,
+    extract::Json(payload): extract::Json<WeeklyCreateDto>,
+) -> Result<Json<ApiResponse<WeeklyDto>>, ApiError> {
+    let count = payload.count.unwrap_or(DEFAULT_WEEKLY_COUNT);
+    if !(1..=MAX_WEEKLY_COUNT).contains(&count) {
+        return Err(ApiError::invalid_fields(vec![FieldError::new(
+            "count",
+            format!("must be between 1 and {}", MAX_WEEKLY_COUNT),
+        )]));
+    }
+    let day = payload.starts_on.unwrap_or_else(|| Utc::now().date_naive());
+    let (starts_at, ends_at) = week_bounds(day);
+    let seed = payload.seed.unwrap_or_else(|| default_seed(day));
+    let raw_filters = raw_filters.unwrap_or_default();
+
+    let pool = db.get_pool();
+    let internal = |err: sqlx::Error| {
+        tracing::error!(error = %err, "failed to create weekly starting {}", starts_at);
+        ApiError::Internal
+    };
+
+    let beatmaps = select_weekly_beatmaps(pool, &filters, seed, count)
+        .await
+        .map_err(internal)?;
+    if beatmaps.is_empty() {
+        return Err(ApiError::validation("No beatmap matches the filters"));
+    }
+
+    if payload.dry_run {
+        return Ok(Json(ApiResponse::ok(
+            "ok",
+            Some(WeeklyDto {
+                id: None,
+                starts_at,
+                ends_at,
+                seed,
+                filters: raw_filters,
+                beatmaps: beatmaps.into_iter().map(Into::into).collect(),
+            }),
+        )));
+    }
+
+    let weekly = insert_weekly(pool, starts_at, ends_at, seed, &raw_filters, &beatmaps)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| {
+            ApiError::Conflict(format!(
+                "A weekly challenge already exists for the week starting {}",
+                starts_at.date()
+            ))
+        })?;
+    let weekly = attach_weekly_beatmaps(pool, vec![weekly])
+        .await
+        .map_err(internal)?
+        .pop();
+
+    Ok(Json(ApiResponse::ok("ok", weekly)))
+}
@@ -10,9 +10,12 @@
 //! - Configuration CORS
 //! - Gestion des erreurs
 
+mod cache;
 mod config;
+mod error;
 mod handlers;
 mod middleware;
+mod models;
 mod routes;
 
 use crate::config::Config;
@@ -21,7 +24,6 @@ use axum::Router;
 use db::db::DatabaseManager;
 use std::net::SocketAddr;
 use tower::ServiceBuilder;
-use tower_http::cors::CorsLayer;
 use tracing::info;
 
 #[tokio::main]
@@ -33,9 +35,24 @@ async fn main() {
         .await
         .expect("Failed to connect to database");
 
+    models::migrate(db.get_pool())
+        .await
+        .expect("Failed to run database migrations");
+
+    if let Some(key) = &config.auth.bootstrap_key {
+        middleware::auth::register_bootstrap_key(db.get_pool(), key)
+            .await
+            .expect("Failed to register bootstrap API key");
+    }
+
+    cache::init(&config.cache);
+    middleware::rate_limit::init(&config.rate_limit);
+
+    let cors = config.cors.layer().expect("Invalid CORS configuration");
+
     let app = Router::new()
-        .merge(routes::create_router(db))
-        .layer(ServiceBuilder::new().layer(CorsLayer::permissive()));
+        .merge(routes::create_router(db, &config.auth))
+        .layer(ServiceBuilder::new().layer(cors));
 
     let app = setup_middleware(app);
 
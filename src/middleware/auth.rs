@@ -0,0 +1,286 @@
+//! Authentification par clé d'API (`Authorization: Bearer <key>`).
+//!
+//! `authenticate` enveloppe toutes les routes `/api` : une clé présente doit
+//! être valide, et la clé reconnue est ajoutée aux extensions de la requête
+//! et de la réponse (pour les logs). `require_scope` protège ensuite les
+//! routes qui en ont besoin ; `admin` donne accès à tout, `import` inclut `read`.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderMap, Request, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use db::db::DatabaseManager;
+use sha2::{Digest, Sha256};
+
+use crate::error::ApiError;
+use crate::models::api_key::query::{ensure_bootstrap_key, find_active_key};
+
+/// Octets aléatoires d'une clé générée
+const KEY_BYTES: usize = 32;
+/// Caractères de la clé gardés en clair dans `prefix`
+pub const KEY_PREFIX_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Read,
+    Import,
+    Admin,
+}
+
+impl Scope {
+    pub const ALLOWED: [&'static str; 3] = ["read", "import", "admin"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "read" => Some(Self::Read),
+            "import" => Some(Self::Import),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Import => "import",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+/// Clé reconnue pour la requête en cours
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: i32,
+    pub scopes: Vec<Scope>,
+}
+
+impl ApiKey {
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|&granted| match granted {
+            Scope::Admin => true,
+            // Un client d'import doit pouvoir suivre ce qu'il importe
+            Scope::Import => matches!(scope, Scope::Import | Scope::Read),
+            Scope::Read => scope == Scope::Read,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthState {
+    pub db: DatabaseManager,
+    pub require_read_key: bool,
+}
+
+/// SHA-256 hexadécimal d'une clé, tel que stocké dans `api_key.key_hash`
+pub fn hash_key(raw: &str) -> String {
+    format!("{:x}", Sha256::digest(raw.as_bytes()))
+}
+
+/// Nouvelle clé : 32 octets aléatoires en hexadécimal
+pub fn generate_key() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; KEY_BYTES];
+    getrandom::fill(&mut bytes)?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Enregistre `AUTH_BOOTSTRAP_KEY` comme clé admin
+pub async fn register_bootstrap_key(pool: &sqlx::PgPool, raw: &str) -> Result<(), sqlx::Error> {
+    let prefix: String = raw.chars().take(KEY_PREFIX_LEN).collect();
+    ensure_bootstrap_key(pool, &hash_key(raw), &prefix).await
+}
+
+/// Vérifie que la clé de la requête a `scope`
+pub fn ensure_scope(key: Option<&ApiKey>, scope: Scope) -> Result<(), ApiError> {
+    match key {
+        None => Err(ApiError::Unauthorized(format!(
+            "An API key with the `{}` scope is required",
+            scope.as_str()
+        ))),
+        Some(key) if !key.allows(scope) => Err(ApiError::Forbidden(format!(
+            "API key lacks the `{}` scope",
+            scope.as_str()
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Clé du header `Authorization`, si présent ; erreur si mal formé.
+/// Le schéma `Bearer` est insensible à la casse (RFC 9110)
+fn bearer_token(headers: &HeaderMap) -> Result<Option<&str>, ApiError> {
+    let Some(value) = headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| v.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim())
+        .filter(|token| !token.is_empty())
+        .map(Some)
+        .ok_or_else(|| ApiError::Unauthorized("Expected `Authorization: Bearer <key>`".into()))
+}
+
+async fn resolve_key(state: &AuthState, headers: &HeaderMap) -> Result<Option<ApiKey>, ApiError> {
+    let Some(token) = bearer_token(headers)? else {
+        return Ok(None);
+    };
+    let (id, scopes) = find_active_key(state.db.get_pool(), &hash_key(token))
+        .await
+        .map_err(|err| {
+            tracing::error!(error = %err, "failed to look up API key");
+            ApiError::Internal
+        })?
+        .ok_or_else(|| ApiError::Unauthorized("Invalid or revoked API key".into()))?;
+
+    Ok(Some(ApiKey {
+        id,
+        scopes: scopes.iter().filter_map(|s| Scope::parse(s)).collect(),
+    }))
+}
+
+/// Reconnaît la clé de la requête ; sans clé, la requête reste anonyme sauf
+/// si `require_read_key` est actif
+pub async fn authenticate(
+    State(state): State<AuthState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let key = match resolve_key(&state, req.headers()).await {
+        Ok(key) => key,
+        Err(err) => return err.into_response(),
+    };
+    if state.require_read_key
+        && let Err(err) = ensure_scope(key.as_ref(), Scope::Read)
+    {
+        return err.into_response();
+    }
+
+    let Some(key) = key else {
+        return next.run(req).await;
+    };
+    req.extensions_mut().insert(key.clone());
+    let mut response = next.run(req).await;
+    response.extensions_mut().insert(key);
+    response
+}
+
+/// Refuse la requête si sa clé n'a pas `scope` (401 sans clé, 403 sinon)
+pub async fn require_scope(State(scope): State<Scope>, req: Request<Body>, next: Next) -> Response {
+    match ensure_scope(req.extensions().get::<ApiKey>(), scope) {
+        Ok(()) => next.run(req).await,
+        Err(err) => err.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(authorization).unwrap(),
+        );
+        headers
+    }
+
+    fn key(scopes: &[Scope]) -> ApiKey {
+        ApiKey {
+            id: 1,
+            scopes: scopes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn bearer_token_is_optional() {
+        assert!(matches!(bearer_token(&HeaderMap::new()), Ok(None)));
+    }
+
+    #[test]
+    fn bearer_token_is_trimmed() {
+        assert!(matches!(
+            bearer_token(&headers("Bearer abc")),
+            Ok(Some("abc"))
+        ));
+        assert!(matches!(
+            bearer_token(&headers("Bearer   abc  ")),
+            Ok(Some("abc"))
+        ));
+    }
+
+    #[test]
+    fn bearer_scheme_is_case_insensitive() {
+        for value in ["bearer abc", "BEARER abc", "bEaReR abc"] {
+            assert!(
+                matches!(bearer_token(&headers(value)), Ok(Some("abc"))),
+                "{value:?} should be accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_authorization_is_rejected() {
+        for value in ["abc", "Basic abc", "Bearerabc", "Bearer", "Bearer    "] {
+            assert!(
+                matches!(
+                    bearer_token(&headers(value)),
+                    Err(ApiError::Unauthorized(_))
+                ),
+                "{value:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_key_is_unauthorized() {
+        assert!(matches!(
+            ensure_scope(None, Scope::Read),
+            Err(ApiError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn key_without_scope_is_forbidden() {
+        let read = key(&[Scope::Read]);
+        assert!(ensure_scope(Some(&read), Scope::Read).is_ok());
+        assert!(matches!(
+            ensure_scope(Some(&read), Scope::Import),
+            Err(ApiError::Forbidden(_))
+        ));
+        assert!(matches!(
+            ensure_scope(Some(&key(&[])), Scope::Read),
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn admin_allows_every_scope() {
+        let admin = key(&[Scope::Admin]);
+        for scope in [Scope::Read, Scope::Import, Scope::Admin] {
+            assert!(admin.allows(scope));
+            assert!(ensure_scope(Some(&admin), scope).is_ok());
+        }
+    }
+
+    #[test]
+    fn import_implies_read() {
+        let import = key(&[Scope::Import]);
+        assert!(import.allows(Scope::Import));
+        assert!(import.allows(Scope::Read));
+        assert!(!import.allows(Scope::Admin));
+        // Avec `require_read_key`, une clé d'import passe `authenticate`
+        assert!(ensure_scope(Some(&import), Scope::Read).is_ok());
+
+        let read = key(&[Scope::Read]);
+        assert!(!read.allows(Scope::Import));
+        assert!(!read.allows(Scope::Admin));
+    }
+}
@@ -0,0 +1,68 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::OriginalUri,
+    http::{HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::cache::{self, CachedResponse};
+
+const X_CACHE: &str = "x-cache";
+
+/// Sert les GET depuis le cache mémoire ; seules les réponses 200 sont stockées.
+/// À poser par route (`get(handler).layer(...)`) sur les lectures cacheables.
+pub async fn cache_response(req: Request<Body>, next: Next) -> Response {
+    let Some(cache) = cache::get() else {
+        return next.run(req).await;
+    };
+    if req.method() != Method::GET {
+        return next.run(req).await;
+    }
+
+    // URI complète : derrière `nest("/api", ...)` le préfixe est retiré de `req.uri()`
+    let uri = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|u| u.0.clone())
+        .unwrap_or_else(|| req.uri().clone());
+    let key = cache::normalize_key(uri.path(), uri.query());
+
+    if let Some(cached) = cache.lookup(&key).await {
+        let mut response = Response::new(Body::from(cached.body));
+        *response.headers_mut() = cached.headers;
+        response
+            .headers_mut()
+            .insert(X_CACHE, HeaderValue::from_static("HIT"));
+        return response;
+    }
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::error!(error = %err, "failed to buffer response for cache");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    cache
+        .store(
+            key,
+            CachedResponse {
+                body: body.clone(),
+                headers: parts.headers.clone(),
+            },
+        )
+        .await;
+
+    parts
+        .headers
+        .insert(X_CACHE, HeaderValue::from_static("MISS"));
+    Response::from_parts(parts, Body::from(body))
+}
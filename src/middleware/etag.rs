@@ -0,0 +1,205 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::State,
+    http::{HeaderValue, Method, Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+
+/// Valeurs de `Cache-Control` par famille de routes
+pub mod cache_control {
+    /// Fiche beatmapset : change seulement quand le set est retraité
+    pub const BEATMAPSET: &str = "public, max-age=300, stale-while-revalidate=60";
+    /// Rates : calculés une fois pour toutes
+    pub const RATE: &str = "public, max-age=3600, stale-while-revalidate=300";
+    /// Listes : bougent à chaque import
+    pub const LIST: &str = "public, max-age=30";
+    /// Difficultés similaires : coûteuses, peu sensibles à un import isolé
+    pub const SIMILAR: &str = "public, max-age=300";
+}
+
+/// ETag fort : SHA-256 du corps sérialisé
+pub fn compute_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("\"{}\"", URL_SAFE_NO_PAD.encode(&digest[..16]))
+}
+
+/// `If-None-Match` : liste d'ETags ou `*` (comparaison faible, RFC 9110 §13.1.2)
+fn matches_if_none_match(header: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    header
+        .split(',')
+        .map(|candidate| candidate.trim())
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// `Cache-Control` d'une réponse : une requête authentifiée ne doit pas être
+/// servie par un cache partagé, `public` y devient `private`
+fn effective_cache_control(cache_control: &'static str, authorized: bool) -> HeaderValue {
+    if !authorized {
+        return HeaderValue::from_static(cache_control);
+    }
+    HeaderValue::from_str(&cache_control.replacen("public", "private", 1))
+        .unwrap_or_else(|_| HeaderValue::from_static("private"))
+}
+
+/// Ajoute `ETag`, `Cache-Control` et `Vary: Authorization` aux GET réussis, et
+/// répond `304 Not Modified` quand le client possède déjà cette version.
+pub async fn conditional_get(
+    State(cache_control): State<&'static str>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return next.run(req).await;
+    }
+
+    let if_none_match = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_owned());
+    let authorized = req.headers().contains_key(header::AUTHORIZATION);
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::error!(error = %err, "failed to buffer response for etag");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = compute_etag(&body);
+    let Ok(etag_value) = HeaderValue::from_str(&etag) else {
+        return Response::from_parts(parts, Body::from(body));
+    };
+    let cache_control = effective_cache_control(cache_control, authorized);
+    let vary = HeaderValue::from_static("authorization");
+
+    if if_none_match.is_some_and(|h| matches_if_none_match(&h, &etag)) {
+        return (
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::ETAG, etag_value),
+                (header::CACHE_CONTROL, cache_control),
+                (header::VARY, vary),
+            ],
+        )
+            .into_response();
+    }
+
+    parts.headers.insert(header::ETAG, etag_value);
+    parts.headers.insert(header::CACHE_CONTROL, cache_control);
+    parts.headers.append(header::VARY, vary);
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, middleware::from_fn_with_state, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/", get(|| async { "body" }))
+            .route(
+                "/missing",
+                get(|| async { (StatusCode::NOT_FOUND, "missing") }),
+            )
+            .layer(from_fn_with_state(cache_control::LIST, conditional_get))
+    }
+
+    async fn send(uri: &str, headers: &[(header::HeaderName, &str)]) -> Response {
+        let mut req = Request::get(uri);
+        for (name, value) in headers {
+            req = req.header(name, *value);
+        }
+        app()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn ok_response_gets_etag_and_cache_headers() {
+        let response = send("/", &[]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::ETAG],
+            compute_etag(b"body").as_str()
+        );
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            cache_control::LIST
+        );
+        assert_eq!(response.headers()[header::VARY], "authorization");
+    }
+
+    #[tokio::test]
+    async fn matching_etag_gives_not_modified() {
+        let etag = compute_etag(b"body");
+        let response = send("/", &[(header::IF_NONE_MATCH, &etag)]).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        let weak = format!("\"other\", W/{}", etag);
+        let response = send("/", &[(header::IF_NONE_MATCH, &weak)]).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = send("/", &[(header::IF_NONE_MATCH, "*")]).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn stale_etag_gives_the_body() {
+        let response = send("/", &[(header::IF_NONE_MATCH, "\"stale\"")]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"body");
+    }
+
+    #[tokio::test]
+    async fn errors_are_left_untouched() {
+        let response = send("/missing", &[(header::IF_NONE_MATCH, "*")]).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::ETAG).is_none());
+        assert!(response.headers().get(header::CACHE_CONTROL).is_none());
+    }
+
+    #[tokio::test]
+    async fn authorized_requests_are_private() {
+        let response = send("/", &[(header::AUTHORIZATION, "Bearer abc")]).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "private, max-age=30"
+        );
+
+        let etag = compute_etag(b"body");
+        let response = send(
+            "/",
+            &[
+                (header::AUTHORIZATION, "Bearer abc"),
+                (header::IF_NONE_MATCH, &etag),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "private, max-age=30"
+        );
+    }
+}
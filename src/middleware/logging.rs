@@ -9,6 +9,10 @@ use axum::{
 use std::time::Instant;
 use tracing::{error, info, warn};
 
+use super::auth::ApiKey;
+use super::rate_limit::request_ip;
+use super::request_id::{RequestId, assign_request_id};
+
 pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
     let path = req.uri().path().to_owned();
     let method = req.method().clone();
@@ -21,29 +25,21 @@ pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
         .map(|p| p.as_str().to_owned())
         .unwrap_or_else(|| path.clone());
 
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone())
+        .unwrap_or_default();
+
     let headers = req.headers();
     let user_agent: String = headers
         .get(header::USER_AGENT)
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_owned())
         .unwrap_or_else(|| "-".to_string());
-    let x_real_ip: String = headers
-        .get("x-real-ip")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_owned())
-        .unwrap_or_default();
-    let x_forwarded_for: String = headers
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_owned())
-        .unwrap_or_default();
-    let client_ip: String = if !x_real_ip.is_empty() {
-        x_real_ip.clone()
-    } else if !x_forwarded_for.is_empty() {
-        x_forwarded_for.clone()
-    } else {
-        "-".to_string()
-    };
+    // Même IP que la limite de débit : `X-Forwarded-For` n'est lu que d'un proxy de confiance
+    let client_ip: String =
+        request_ip(headers, req.extensions()).map_or_else(|| "-".to_string(), |ip| ip.to_string());
 
     // Redaction: n'expose pas les valeurs sensibles, seulement leur présence
     let has_auth = headers.get(header::AUTHORIZATION).is_some();
@@ -54,10 +50,15 @@ pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
     let duration = start.elapsed();
     let duration_ms = duration.as_millis();
     let status = response.status();
+    let api_key_id: String = response
+        .extensions()
+        .get::<ApiKey>()
+        .map_or_else(|| "-".to_string(), |key| key.id.to_string());
 
     // Niveau: warn si requête lente (>=100ms), error si 5xx, sinon info
     if status.is_server_error() {
         error!(
+            request_id = %request_id,
             method = %method,
             path = %path,
             endpoint = %matched_endpoint,
@@ -67,11 +68,13 @@ pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
             user_agent = %user_agent,
             client_ip = %client_ip,
             auth_present = has_auth,
+            api_key_id = %api_key_id,
             cookie_present = has_cookie,
             "request completed with server error"
         );
     } else if duration_ms >= 100 {
         warn!(
+            request_id = %request_id,
             method = %method,
             path = %path,
             endpoint = %matched_endpoint,
@@ -81,11 +84,13 @@ pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
             user_agent = %user_agent,
             client_ip = %client_ip,
             auth_present = has_auth,
+            api_key_id = %api_key_id,
             cookie_present = has_cookie,
             "slow request"
         );
     } else {
         info!(
+            request_id = %request_id,
             method = %method,
             path = %path,
             endpoint = %matched_endpoint,
@@ -95,6 +100,7 @@ pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
             user_agent = %user_agent,
             client_ip = %client_ip,
             auth_present = has_auth,
+            api_key_id = %api_key_id,
             cookie_present = has_cookie,
             "request completed"
         );
@@ -108,5 +114,7 @@ pub fn setup_middleware<S>(app: axum::Router<S>) -> axum::Router<S>
 where
     S: Clone + Send + Sync + 'static,
 {
+    // request_id en dernier : il enveloppe le logging et les handlers
     app.layer(middleware::from_fn(track_execution_time))
+        .layer(middleware::from_fn(assign_request_id))
 }
@@ -1 +1,6 @@
+pub mod auth;
+pub mod cache;
+pub mod etag;
 pub mod logging;
+pub mod rate_limit;
+pub mod request_id;
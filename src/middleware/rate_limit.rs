@@ -0,0 +1,448 @@
+//! Limite de débit par client (token bucket).
+//!
+//! Un client est identifié par sa clé d'API si la requête en a une, sinon par
+//! son IP. Chaque client a un seau par groupe de routes, qui se remplit en
+//! continu jusqu'à la limite par minute. Les réponses portent les headers
+//! `RateLimit-Limit`, `RateLimit-Remaining` et `RateLimit-Reset` ; un seau vide
+//! donne un 429 avec `Retry-After`.
+//!
+//! `pre_limit` passe avant l'authentification : les requêtes avec un header
+//! `Authorization` y sont comptées par IP, pour qu'une rafale de clés
+//! invalides ne déclenche pas autant de lectures en base.
+//!
+//! L'IP est celle de la connexion ; `X-Forwarded-For` n'est lu que si la
+//! connexion vient d'un proxy de `trusted_proxies`.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Extensions, HeaderMap, HeaderName, HeaderValue, Method, Request, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use moka::future::Cache;
+use once_cell::sync::OnceCell;
+
+use super::auth::ApiKey;
+use crate::config::RateLimitConfig;
+use crate::error::ApiError;
+
+static RATE_LIMITER: OnceCell<RateLimiter> = OnceCell::new();
+
+/// Proxies dont on lit `X-Forwarded-For`, connus même si la limite est désactivée
+static TRUSTED_PROXIES: OnceCell<Vec<IpAddr>> = OnceCell::new();
+
+/// Routes coûteuses, limitées à `strict_per_minute` ; `{…}` remplace un segment
+const STRICT_PATHS: [&str; 5] = [
+    "/api/beatmaps/imports",
+    "/api/beatmapsets/random",
+    "/api/beatmaps/{beatmap_osu_id}/similar",
+    "/api/players/skill",
+    "/api/recommendations",
+];
+
+/// Un seau inactif plus longtemps qu'une minute est de nouveau plein : on peut l'oublier
+const IDLE_EVICTION: Duration = Duration::from_secs(120);
+
+static RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+static RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+static RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RouteGroup {
+    Read,
+    Write,
+    Strict,
+    /// Requêtes avec une clé, avant sa vérification
+    Auth,
+}
+
+/// `path` correspond au gabarit de route `pattern`
+fn matches_route(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.split('/');
+    let mut path = path.split('/');
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some(expected), Some(segment)) => {
+                let wildcard = expected.starts_with('{') && !segment.is_empty();
+                if !wildcard && expected != segment {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+impl RouteGroup {
+    fn classify(method: &Method, path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+        if STRICT_PATHS
+            .iter()
+            .any(|pattern| matches_route(pattern, path))
+        {
+            Self::Strict
+        } else if method == Method::GET || method == Method::HEAD {
+            Self::Read
+        } else {
+            Self::Write
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Strict => "strict",
+            Self::Auth => "auth",
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Résultat d'un passage au seau
+struct Decision {
+    allowed: bool,
+    limit: u32,
+    remaining: u32,
+    /// Secondes avant que le seau soit de nouveau plein
+    reset: u64,
+    /// Secondes avant le prochain jeton, si refusé
+    retry_after: u64,
+}
+
+impl Bucket {
+    fn take(&mut self, limit: u32, now: Instant) -> Decision {
+        let capacity = f64::from(limit);
+        let per_second = capacity / 60.0;
+
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * per_second).min(capacity);
+        self.updated = now;
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+
+        Decision {
+            allowed,
+            limit,
+            remaining: self.tokens.floor() as u32,
+            reset: ((capacity - self.tokens) / per_second).ceil() as u64,
+            retry_after: (((1.0 - self.tokens) / per_second).ceil() as u64).max(1),
+        }
+    }
+}
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Cache<String, Arc<Mutex<Bucket>>>,
+}
+
+/// Initialise la limite globale ; sans appel (ou si désactivée), le middleware laisse tout passer
+pub fn init(config: &RateLimitConfig) {
+    let _ = TRUSTED_PROXIES.set(config.trusted_proxies.clone());
+    if !config.enabled {
+        return;
+    }
+    let buckets = Cache::builder()
+        .max_capacity(config.max_clients)
+        .time_to_idle(IDLE_EVICTION)
+        .build();
+
+    let _ = RATE_LIMITER.set(RateLimiter {
+        config: config.clone(),
+        buckets,
+    });
+}
+
+impl RateLimiter {
+    fn limit(&self, group: RouteGroup) -> u32 {
+        match group {
+            RouteGroup::Read => self.config.read_per_minute,
+            RouteGroup::Write => self.config.write_per_minute,
+            RouteGroup::Strict => self.config.strict_per_minute,
+            RouteGroup::Auth => self.config.auth_per_minute,
+        }
+    }
+
+    async fn check(&self, client: &str, group: RouteGroup) -> Decision {
+        let limit = self.limit(group);
+        let key = format!("{}|{}", group.as_str(), client);
+        let bucket = self
+            .buckets
+            .get_with(key, async move {
+                Arc::new(Mutex::new(Bucket {
+                    tokens: f64::from(limit),
+                    updated: Instant::now(),
+                }))
+            })
+            .await;
+
+        let mut bucket = bucket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        bucket.take(limit, Instant::now())
+    }
+}
+
+/// IP du client : celle de la connexion, ou le dernier saut de `X-Forwarded-For`
+/// si la connexion vient d'un proxy de confiance
+fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>, trusted: &[IpAddr]) -> Option<IpAddr> {
+    let peer = peer?;
+    if !trusted.contains(&peer) {
+        return Some(peer);
+    }
+    let forwarded = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .next_back()
+        .and_then(|hop| hop.trim().parse().ok());
+    Some(forwarded.unwrap_or(peer))
+}
+
+/// IP du client d'une requête, selon les proxies de confiance configurés
+pub fn request_ip(headers: &HeaderMap, extensions: &Extensions) -> Option<IpAddr> {
+    let peer = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let trusted = TRUSTED_PROXIES.get().map(Vec::as_slice).unwrap_or_default();
+    client_ip(headers, peer, trusted)
+}
+
+fn ip_id(headers: &HeaderMap, extensions: &Extensions) -> String {
+    match request_ip(headers, extensions) {
+        Some(ip) => format!("ip:{}", ip),
+        None => "ip:-".to_string(),
+    }
+}
+
+/// Identifiant du client : clé d'API, sinon IP
+pub fn client_key(headers: &HeaderMap, extensions: &Extensions) -> String {
+    match extensions.get::<ApiKey>() {
+        Some(key) => format!("key:{}", key.id),
+        None => ip_id(headers, extensions),
+    }
+}
+
+fn insert_headers(headers: &mut HeaderMap, decision: &Decision) {
+    headers.insert(RATELIMIT_LIMIT.clone(), HeaderValue::from(decision.limit));
+    headers.insert(
+        RATELIMIT_REMAINING.clone(),
+        HeaderValue::from(decision.remaining),
+    );
+    headers.insert(RATELIMIT_RESET.clone(), HeaderValue::from(decision.reset));
+}
+
+/// Consomme un jeton du seau (client, groupe de routes) ; 429 si le seau est vide
+pub async fn rate_limit(req: Request<Body>, next: Next) -> Response {
+    let Some(limiter) = RATE_LIMITER.get() else {
+        return next.run(req).await;
+    };
+
+    let group = RouteGroup::classify(req.method(), req.uri().path());
+    let client = client_key(req.headers(), req.extensions());
+    limit(limiter, client, group, req, next).await
+}
+
+/// Avant l'authentification : compte par IP les requêtes qui portent une clé
+pub async fn pre_limit(req: Request<Body>, next: Next) -> Response {
+    let Some(limiter) = RATE_LIMITER.get() else {
+        return next.run(req).await;
+    };
+    if !req.headers().contains_key(header::AUTHORIZATION) {
+        return next.run(req).await;
+    }
+
+    let client = ip_id(req.headers(), req.extensions());
+    limit(limiter, client, RouteGroup::Auth, req, next).await
+}
+
+async fn limit(
+    limiter: &RateLimiter,
+    client: String,
+    group: RouteGroup,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let decision = limiter.check(&client, group).await;
+
+    let mut response = if decision.allowed {
+        next.run(req).await
+    } else {
+        tracing::warn!(
+            client = %client,
+            group = group.as_str(),
+            retry_after = decision.retry_after,
+            "rate limit exceeded"
+        );
+        ApiError::RateLimited {
+            retry_after: decision.retry_after,
+        }
+        .into_response()
+    };
+    insert_headers(response.headers_mut(), &decision);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_bucket(limit: u32, now: Instant) -> Bucket {
+        Bucket {
+            tokens: f64::from(limit),
+            updated: now,
+        }
+    }
+
+    #[test]
+    fn take_consumes_one_token() {
+        let now = Instant::now();
+        let mut bucket = full_bucket(60, now);
+        let decision = bucket.take(60, now);
+        assert!(decision.allowed);
+        assert_eq!(decision.limit, 60);
+        assert_eq!(decision.remaining, 59);
+        // Un jeton par seconde à 60/min
+        assert_eq!(decision.reset, 1);
+    }
+
+    #[test]
+    fn empty_bucket_refuses_until_refilled() {
+        let now = Instant::now();
+        let mut bucket = full_bucket(2, now);
+        assert!(bucket.take(2, now).allowed);
+        assert!(bucket.take(2, now).allowed);
+
+        let refused = bucket.take(2, now);
+        assert!(!refused.allowed);
+        assert_eq!(refused.remaining, 0);
+        assert_eq!(refused.retry_after, 30);
+        assert_eq!(refused.reset, 60);
+
+        assert!(!bucket.take(2, now + Duration::from_secs(29)).allowed);
+        assert!(bucket.take(2, now + Duration::from_secs(30)).allowed);
+    }
+
+    #[test]
+    fn refill_is_capped_at_the_limit() {
+        let now = Instant::now();
+        let mut bucket = full_bucket(10, now);
+        bucket.take(10, now);
+        let decision = bucket.take(10, now + Duration::from_secs(3600));
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 9);
+    }
+
+    #[test]
+    fn retry_after_is_at_least_one_second() {
+        let now = Instant::now();
+        let mut bucket = Bucket {
+            tokens: 0.999,
+            updated: now,
+        };
+        let decision = bucket.take(6000, now);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, 1);
+    }
+
+    fn forwarded(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn ip(raw: &str) -> IpAddr {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarded_for_is_ignored_from_untrusted_peers() {
+        let headers = forwarded("1.1.1.1");
+        assert_eq!(
+            client_ip(&headers, Some(ip("10.0.0.2")), &[]),
+            Some(ip("10.0.0.2"))
+        );
+        assert_eq!(
+            client_ip(&headers, Some(ip("10.0.0.2")), &[ip("10.0.0.1")]),
+            Some(ip("10.0.0.2"))
+        );
+    }
+
+    #[test]
+    fn trusted_proxy_yields_the_last_hop() {
+        let trusted = [ip("10.0.0.1")];
+        assert_eq!(
+            client_ip(
+                &forwarded("6.6.6.6, 2.2.2.2"),
+                Some(ip("10.0.0.1")),
+                &trusted
+            ),
+            Some(ip("2.2.2.2"))
+        );
+        assert_eq!(
+            client_ip(&HeaderMap::new(), Some(ip("10.0.0.1")), &trusted),
+            Some(ip("10.0.0.1"))
+        );
+        assert_eq!(
+            client_ip(&forwarded("not-an-ip"), Some(ip("10.0.0.1")), &trusted),
+            Some(ip("10.0.0.1"))
+        );
+    }
+
+    #[test]
+    fn expensive_routes_are_strict() {
+        for (method, path) in [
+            (Method::POST, "/api/beatmaps/imports"),
+            (Method::GET, "/api/beatmapsets/random"),
+            (Method::GET, "/api/beatmaps/123456/similar"),
+            (Method::GET, "/api/beatmaps/123456/similar/"),
+            (Method::POST, "/api/players/skill"),
+            (Method::GET, "/api/recommendations"),
+        ] {
+            assert_eq!(
+                RouteGroup::classify(&method, path),
+                RouteGroup::Strict,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn other_routes_are_grouped_by_method() {
+        for path in [
+            "/api/beatmaps/123456",
+            "/api/beatmaps//similar",
+            "/api/beatmaps/123456/similar/extra",
+            "/api/recommendations/123",
+        ] {
+            assert_eq!(
+                RouteGroup::classify(&Method::GET, path),
+                RouteGroup::Read,
+                "{path}"
+            );
+        }
+        assert_eq!(
+            RouteGroup::classify(&Method::POST, "/api/scores"),
+            RouteGroup::Write
+        );
+    }
+
+    #[test]
+    fn no_peer_means_no_ip() {
+        assert_eq!(client_ip(&forwarded("1.1.1.1"), None, &[]), None);
+    }
+}
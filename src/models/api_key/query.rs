@@ -0,0 +1,82 @@
+use sqlx::PgPool;
+
+use super::types::ApiKeyDto;
+
+const API_KEY_COLUMNS: &str = "id, name, prefix, scopes, created_at, revoked_at";
+
+/// Id et scopes d'une clé active à partir de son hash
+pub async fn find_active_key(
+    pool: &PgPool,
+    key_hash: &str,
+) -> Result<Option<(i32, Vec<String>)>, sqlx::Error> {
+    sqlx::query_as::<_, (i32, Vec<String>)>(
+        "SELECT id, scopes FROM api_key WHERE key_hash = $1 AND revoked_at IS NULL",
+    )
+    .bind(key_hash)
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn insert_api_key(
+    pool: &PgPool,
+    name: &str,
+    key_hash: &str,
+    prefix: &str,
+    scopes: &[String],
+) -> Result<ApiKeyDto, sqlx::Error> {
+    let sql = format!(
+        r#"
+        INSERT INTO api_key (name, key_hash, prefix, scopes)
+        VALUES ($1, $2, $3, $4)
+        RETURNING {API_KEY_COLUMNS}
+        "#
+    );
+    sqlx::query_as::<_, ApiKeyDto>(&sql)
+        .bind(name)
+        .bind(key_hash)
+        .bind(prefix)
+        .bind(scopes)
+        .fetch_one(pool)
+        .await
+}
+
+/// Enregistre la clé admin de la configuration si elle ne l'est pas déjà
+pub async fn ensure_bootstrap_key(
+    pool: &PgPool,
+    key_hash: &str,
+    prefix: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO api_key (name, key_hash, prefix, scopes)
+        VALUES ('bootstrap', $1, $2, ARRAY['admin'])
+        ON CONFLICT (key_hash) DO NOTHING
+        "#,
+    )
+    .bind(key_hash)
+    .bind(prefix)
+    .execute(pool)
+    .await
+    .map(|_| ())
+}
+
+pub async fn find_api_keys(pool: &PgPool) -> Result<Vec<ApiKeyDto>, sqlx::Error> {
+    let sql = format!("SELECT {API_KEY_COLUMNS} FROM api_key ORDER BY id");
+    sqlx::query_as::<_, ApiKeyDto>(&sql).fetch_all(pool).await
+}
+
+/// Révoque une clé ; `None` si elle n'existe pas
+pub async fn revoke_api_key(pool: &PgPool, id: i32) -> Result<Option<ApiKeyDto>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        UPDATE api_key
+        SET revoked_at = COALESCE(revoked_at, NOW())
+        WHERE id = $1
+        RETURNING {API_KEY_COLUMNS}
+        "#
+    );
+    sqlx::query_as::<_, ApiKeyDto>(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
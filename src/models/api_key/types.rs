@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longueur maximale du nom d'une clé
+pub const MAX_KEY_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct ApiKeyDto {
+    pub id: i32,
+    #[schema(example = "import pipeline")]
+    pub name: String,
+    /// Début de la clé
+    #[schema(example = "3f9a1c07")]
+    pub prefix: String,
+    #[schema(example = json!(["import"]))]
+    pub scopes: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApiKeyCreateDto {
+    #[schema(example = "import pipeline")]
+    pub name: String,
+    /// Parmi `read`, `import` (qui inclut `read`) et `admin` (qui donne tout)
+    #[schema(example = json!(["read", "import"]))]
+    pub scopes: Vec<String>,
+}
+
+/// Clé créée ; `key` n'est retournée qu'une seule fois
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeyCreatedDto {
+    #[serde(flatten)]
+    pub api_key: ApiKeyDto,
+    /// À envoyer dans `Authorization: Bearer <key>`
+    pub key: String,
+}
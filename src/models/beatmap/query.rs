@@ -0,0 +1,180 @@
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use super::types::{
+    BeatmapHashRow, BeatmapWithSetRow, SimilarBeatmapRow, SimilarityConstraints, SimilaritySource,
+};
+use crate::models::beatmapset::query::push_rate_scope;
+
+/// Sélection commune de `BeatmapWithSetRow`, à compléter par un `WHERE`
+const BEATMAP_WITH_SET: &str = r#"
+    SELECT b.id, b.file_md5, b.osu_id, b.difficulty, b.status, b.od, b.bpm, b.total_time,
+           bs.osu_id AS beatmapset_osu_id, bs.artist, bs.title, bs.creator
+    FROM beatmap b
+    JOIN beatmapset bs ON bs.id = b.beatmapset_id
+"#;
+
+/// Difficultés traitées correspondant aux MD5 donnés ; les hashes sans beatmap ne sont pas retournés
+pub async fn find_by_hashes(
+    pool: &PgPool,
+    hashes: &[String],
+) -> Result<Vec<BeatmapHashRow>, sqlx::Error> {
+    sqlx::query_as::<_, BeatmapHashRow>(
+        r#"
+        SELECT b.file_md5, b.osu_id, bs.osu_id AS beatmapset_osu_id
+        FROM beatmap b
+        JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE b.file_md5 = ANY($1)
+        "#,
+    )
+    .bind(hashes)
+    .fetch_all(pool)
+    .await
+}
+
+/// Difficulté par id osu!, avec son beatmapset
+pub async fn find_by_osu_id(
+    pool: &PgPool,
+    beatmap_osu_id: i32,
+) -> Result<Option<BeatmapWithSetRow>, sqlx::Error> {
+    let sql = format!("{BEATMAP_WITH_SET} WHERE b.osu_id = $1");
+    sqlx::query_as::<_, BeatmapWithSetRow>(&sql)
+        .bind(beatmap_osu_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Difficulté source d'une recherche de similaires, son rate au centirate
+/// donné et le rating de ce rate pour `rating_type`
+pub async fn find_similarity_source(
+    pool: &PgPool,
+    beatmap_osu_id: i32,
+    centirate: i32,
+    rating_type: &str,
+) -> Result<Option<SimilaritySource>, sqlx::Error> {
+    sqlx::query_as::<_, SimilaritySource>(
+        r#"
+        SELECT b.id AS beatmap_id, b.status, r.id AS rates_id, rt.rating
+        FROM beatmap b
+        LEFT JOIN rates r ON r.beatmap_id = b.id AND r.centirate = $2
+        LEFT JOIN rating rt ON rt.rates_id = r.id AND rt.rating_type = $3
+        WHERE b.osu_id = $1
+        "#,
+    )
+    .bind(beatmap_osu_id)
+    .bind(centirate)
+    .bind(rating_type)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Difficultés les plus proches du rate source. Le profil est le vecteur
+/// rating (du type choisi) + skillsets ; seuls les rates candidats ayant
+/// toutes les dimensions du profil source sont comparés.
+///
+/// Les candidats sont d'abord restreints à une fenêtre de rating autour de la
+/// source, puis chaque dimension est divisée par son écart-type parmi eux pour
+/// qu'aucun skillset ne domine la distance.
+pub async fn find_similar(
+    pool: &PgPool,
+    source: &SimilaritySource,
+    source_rates_id: i32,
+    constraints: &SimilarityConstraints,
+) -> Result<Vec<SimilarBeatmapRow>, sqlx::Error> {
+    let mut qb = QueryBuilder::<Postgres>::new(
+        r#"
+        WITH source_profile AS (
+            SELECT s.pattern_type AS dim, s.pattern AS value
+            FROM skillset s
+            WHERE s.rates_id = "#,
+    );
+    qb.push_bind(source_rates_id).push(
+        r#"
+            UNION ALL
+            SELECT 'rating', rt.rating
+            FROM rating rt
+            WHERE rt.rates_id = "#,
+    );
+    qb.push_bind(source_rates_id)
+        .push(" AND rt.rating_type = ")
+        .push_bind(constraints.rating_type.clone())
+        .push(
+            r#"
+        ),
+        source_rating AS (
+            SELECT value FROM source_profile WHERE dim = 'rating'
+        ),
+        candidates AS (
+            SELECT b.id AS beatmap_id, r.id AS rates_id, r.centirate
+            FROM beatmap b
+            JOIN rates r ON r.beatmap_id = b.id
+            JOIN rating cr ON cr.rates_id = r.id AND cr.rating_type = "#,
+        );
+    qb.push_bind(constraints.rating_type.clone()).push(
+        r#"
+            JOIN source_rating sr
+              ON cr.rating BETWEEN sr.value * (1 - "#,
+    );
+    qb.push_bind(constraints.rating_window)
+        .push(") AND sr.value * (1 + ")
+        .push_bind(constraints.rating_window)
+        .push(") WHERE b.id <> ");
+    qb.push_bind(source.beatmap_id).push(" AND ");
+    push_rate_scope(&mut qb, Some(constraints.rate));
+    if let Some(status) = &constraints.status {
+        qb.push(" AND b.status = ").push_bind(status.clone());
+    }
+    if let Some(v) = constraints.bpm_min {
+        qb.push(" AND b.bpm >= ").push_bind(v);
+    }
+    if let Some(v) = constraints.bpm_max {
+        qb.push(" AND b.bpm <= ").push_bind(v);
+    }
+    qb.push(
+        r#"
+        ),
+        candidate_profile AS (
+            SELECT c.rates_id, s.pattern_type AS dim, s.pattern AS value
+            FROM candidates c
+            JOIN skillset s ON s.rates_id = c.rates_id
+            UNION ALL
+            SELECT c.rates_id, 'rating', rt.rating
+            FROM candidates c
+            JOIN rating rt ON rt.rates_id = c.rates_id AND rt.rating_type = "#,
+    );
+    qb.push_bind(constraints.rating_type.clone()).push(
+        r#"
+        ),
+        dim_scale AS (
+            SELECT dim, COALESCE(NULLIF(STDDEV_POP(value), 0), 1) AS scale
+            FROM candidate_profile
+            GROUP BY dim
+        ),
+        distances AS (
+            SELECT cp.rates_id,
+                   SQRT(SUM(POWER((cp.value - sp.value) / ds.scale, 2)))::float8 AS distance
+            FROM candidate_profile cp
+            JOIN source_profile sp ON sp.dim = cp.dim
+            JOIN dim_scale ds ON ds.dim = cp.dim
+            GROUP BY cp.rates_id
+            HAVING COUNT(*) = (SELECT COUNT(*) FROM source_profile)
+        ),
+        closest AS (
+            SELECT DISTINCT ON (c.beatmap_id) c.beatmap_id, c.centirate, d.distance
+            FROM distances d
+            JOIN candidates c ON c.rates_id = d.rates_id
+            ORDER BY c.beatmap_id, d.distance, c.centirate
+        )
+        SELECT b.osu_id AS beatmap_osu_id, bs.osu_id AS beatmapset_osu_id,
+               closest.centirate, closest.distance
+        FROM closest
+        JOIN beatmap b ON b.id = closest.beatmap_id
+        JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        ORDER BY closest.distance, b.id
+        LIMIT "#,
+    );
+    qb.push_bind(constraints.limit);
+
+    qb.build_query_as::<SimilarBeatmapRow>()
+        .fetch_all(pool)
+        .await
+}
@@ -0,0 +1,225 @@
+use dto::models::beatmaps::simple::types::{Beatmap, Beatmapset};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::beatmapset::types::RateScope;
+use crate::models::pending_beatmap::types::HashStatusDto;
+use crate::models::rate::types::RateDetailDto;
+
+/// Difficulté avec les métadonnées de son beatmapset
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BeatmapWithSetRow {
+    pub id: i32,
+    pub file_md5: String,
+    pub osu_id: Option<i32>,
+    pub difficulty: String,
+    pub status: String,
+    pub od: f64,
+    pub bpm: f64,
+    pub total_time: i32,
+    pub beatmapset_osu_id: Option<i32>,
+    pub artist: String,
+    pub title: String,
+    pub creator: String,
+}
+
+impl BeatmapWithSetRow {
+    pub fn into_parts(self) -> (BeatmapSummary, BeatmapsetSummary) {
+        (
+            BeatmapSummary {
+                osu_id: self.osu_id,
+                file_md5: self.file_md5,
+                difficulty: self.difficulty,
+                status: self.status,
+                od: self.od,
+                bpm: self.bpm,
+                total_time: self.total_time,
+            },
+            BeatmapsetSummary {
+                osu_id: self.beatmapset_osu_id,
+                artist: self.artist,
+                title: self.title,
+                creator: self.creator,
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BeatmapSummary {
+    pub osu_id: Option<i32>,
+    pub file_md5: String,
+    pub difficulty: String,
+    pub status: String,
+    pub od: f64,
+    pub bpm: f64,
+    /// Durée totale en millisecondes
+    pub total_time: i32,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BeatmapsetSummary {
+    pub osu_id: Option<i32>,
+    pub artist: String,
+    pub title: String,
+    pub creator: String,
+}
+
+/// Difficulté traitée trouvée par son MD5
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BeatmapHashRow {
+    pub file_md5: String,
+    pub osu_id: Option<i32>,
+    pub beatmapset_osu_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LookupStatus {
+    /// Beatmap traitée : `beatmap` et `beatmapset` sont renseignés
+    Found,
+    /// Dans la file d'attente : voir `progress`
+    Pending,
+    /// Le traitement a échoué : voir `progress.failure_reason`
+    Failed,
+    /// Inconnu ou en échec, ajouté à la file par cette requête (`enqueue: true`)
+    Queued,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HashLookupDto {
+    pub osu_hash: String,
+    pub status: LookupStatus,
+    /// La difficulté, comme dans `GET /api/beatmapsets/{osu_id}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beatmap: Option<Beatmap>,
+    /// Son beatmapset, comme `GET /api/beatmapsets/{osu_id}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beatmapset: Option<Beatmapset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<HashStatusDto>,
+}
+
+impl HashLookupDto {
+    pub fn without_match(osu_hash: String, status: LookupStatus) -> Self {
+        Self {
+            osu_hash,
+            status,
+            beatmap: None,
+            beatmapset: None,
+            progress: None,
+        }
+    }
+
+    pub fn found(osu_hash: String, beatmap: Beatmap, beatmapset: Beatmapset) -> Self {
+        Self {
+            beatmap: Some(beatmap),
+            beatmapset: Some(beatmapset),
+            ..Self::without_match(osu_hash, LookupStatus::Found)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct HashLookupRequestDto {
+    /// MD5 des fichiers `.osu`
+    pub hashes: Vec<String>,
+    /// Ajoute les hashes inconnus ou en échec à la file de traitement
+    #[serde(default)]
+    pub enqueue: bool,
+}
+
+/// Lien vers le beatmapset parent
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BeatmapsetLink {
+    #[serde(flatten)]
+    pub summary: BeatmapsetSummary,
+    #[schema(example = "/api/beatmapsets/123456")]
+    pub href: Option<String>,
+}
+
+/// Une difficulté : métadonnées, données techniques et tous ses rates
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BeatmapDetailDto {
+    #[serde(flatten)]
+    pub beatmap: BeatmapSummary,
+    pub beatmapset: BeatmapsetLink,
+    /// Rates stockés par centirate croissant, avec ratings et skillsets
+    pub rates: Vec<RateDetailDto>,
+}
+
+impl BeatmapDetailDto {
+    pub fn new(row: BeatmapWithSetRow, rates: Vec<RateDetailDto>) -> Self {
+        let (beatmap, summary) = row.into_parts();
+        Self {
+            beatmap,
+            beatmapset: BeatmapsetLink {
+                href: summary.osu_id.map(|id| format!("/api/beatmapsets/{}", id)),
+                summary,
+            },
+            rates,
+        }
+    }
+}
+
+/// Rate de référence d'une recherche de difficultés similaires
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SimilaritySource {
+    pub beatmap_id: i32,
+    pub status: String,
+    /// Absent si la difficulté n'a pas de rate stocké au centirate demandé
+    pub rates_id: Option<i32>,
+    /// Rating du type demandé à ce rate ; absent s'il n'est pas calculé
+    pub rating: Option<f64>,
+}
+
+/// Contraintes sur les difficultés candidates
+#[derive(Debug, Clone)]
+pub struct SimilarityConstraints {
+    pub rating_type: String,
+    pub status: Option<String>,
+    pub bpm_min: Option<f64>,
+    pub bpm_max: Option<f64>,
+    /// Rates des candidates comparés au profil source ; le plus proche est retenu
+    pub rate: RateScope,
+    /// Écart relatif maximal entre le rating d'un candidat et celui de la source
+    pub rating_window: f64,
+    pub limit: i64,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SimilarBeatmapRow {
+    pub beatmap_osu_id: Option<i32>,
+    pub beatmapset_osu_id: Option<i32>,
+    pub centirate: i32,
+    pub distance: f64,
+}
+
+/// Difficulté proche du profil source, au rate où elle l'est le plus
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SimilarBeatmapDto {
+    /// La difficulté, comme dans `GET /api/beatmapsets/{osu_id}`
+    pub beatmap: Beatmap,
+    /// Son beatmapset, comme `GET /api/beatmapsets/{osu_id}`
+    pub beatmapset: Beatmapset,
+    /// Rate de la difficulté auquel la distance est mesurée
+    pub centirate: i32,
+    /// Distance euclidienne entre les vecteurs rating + skillsets, chaque dimension réduite par son écart-type
+    pub distance: f64,
+    /// `1 / (1 + distance)` : 1 pour un profil identique
+    #[schema(example = 0.82)]
+    pub similarity: f64,
+}
+
+impl SimilarBeatmapDto {
+    pub fn new(row: &SimilarBeatmapRow, beatmap: Beatmap, beatmapset: Beatmapset) -> Self {
+        Self {
+            beatmap,
+            beatmapset,
+            centirate: row.centirate,
+            distance: row.distance,
+            similarity: 1.0 / (1.0 + row.distance),
+        }
+    }
+}
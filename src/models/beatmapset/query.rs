@@ -0,0 +1,749 @@
+use std::collections::{BTreeMap, HashMap};
+
+use dto::models::beatmaps::simple::query::find_by_osu_ids::find_by_osu_ids;
+use dto::models::beatmaps::simple::types::Beatmapset;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use super::types::{
+    BeatmapsetListItem, BeatmapsetPageRow, CursorDirection, ListFilters, MatchMode, PageStart,
+    RateScope, SkillsetBound, Sort, SortField, SortOrder,
+};
+
+/// Type de rating utilisé quand la requête n'en précise pas
+pub const DEFAULT_RATING_TYPE: &str = "overall";
+
+/// Rate de référence pour les filtres rating/skillset/drain time sans `rates[centirate]`
+pub const REFERENCE_CENTIRATE: i32 = 100;
+
+/// Pivots tirés par beatmapset demandé à `find_random_beatmapset_osu_ids`,
+/// plusieurs pivots pouvant tomber sur le même set
+const RANDOM_OVERSAMPLE: i64 = 3;
+
+/// Ids osu! des difficultés d'un beatmapset
+pub async fn find_beatmap_osu_ids(
+    pool: &PgPool,
+    beatmapset_osu_id: i32,
+) -> Result<Vec<i32>, sqlx::Error> {
+    sqlx::query_scalar::<_, i32>(
+        r#"
+        SELECT b.osu_id
+        FROM beatmap b
+        JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE bs.osu_id = $1
+        "#,
+    )
+    .bind(beatmapset_osu_id)
+    .fetch_all(pool)
+    .await
+}
+
+fn like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+fn has_difficulty_filters(filters: &ListFilters) -> bool {
+    filters.total_time_min.is_some()
+        || filters.total_time_max.is_some()
+        || filters.bpm_min.is_some()
+        || filters.bpm_max.is_some()
+        || filters.rating_min.is_some()
+        || filters.rating_max.is_some()
+        || !filters.skillsets.is_empty()
+        || !filters.statuses.is_empty()
+        || filters.od_min.is_some()
+        || filters.od_max.is_some()
+        || filters.drain_time_min.is_some()
+        || filters.drain_time_max.is_some()
+        || filters.rate.is_some()
+}
+
+/// Condition sur `r.centirate` : le rate demandé, la fenêtre, ou le rate de référence
+pub fn push_rate_scope(qb: &mut QueryBuilder<'_, Postgres>, scope: Option<RateScope>) {
+    match scope.unwrap_or(RateScope::At(REFERENCE_CENTIRATE)) {
+        RateScope::At(centirate) => {
+            qb.push("r.centirate = ").push_bind(centirate);
+        }
+        RateScope::Within { min, max } => {
+            qb.push("r.centirate BETWEEN ")
+                .push_bind(min)
+                .push(" AND ")
+                .push_bind(max);
+        }
+    }
+}
+
+fn push_skillset_bound(qb: &mut QueryBuilder<'_, Postgres>, bound: &SkillsetBound) {
+    qb.push("EXISTS (SELECT 1 FROM skillset s WHERE s.rates_id = r.id");
+    if let Some(v) = &bound.pattern_type {
+        qb.push(" AND s.pattern_type = ").push_bind(v.clone());
+    }
+    if let Some(v) = bound.min {
+        qb.push(" AND s.pattern >= ").push_bind(v);
+    }
+    if let Some(v) = bound.max {
+        qb.push(" AND s.pattern <= ").push_bind(v);
+    }
+    qb.push(")");
+}
+
+/// Conditions sur une difficulté `b` et un de ses rates `r`
+pub fn push_difficulty_conditions(qb: &mut QueryBuilder<'_, Postgres>, filters: &ListFilters) {
+    qb.push(" AND ");
+    push_rate_scope(qb, filters.rate);
+
+    if let Some(v) = filters.total_time_min {
+        qb.push(" AND b.total_time >= ").push_bind(v);
+    }
+    if let Some(v) = filters.total_time_max {
+        qb.push(" AND b.total_time <= ").push_bind(v);
+    }
+    if let Some(v) = filters.bpm_min {
+        qb.push(" AND b.bpm >= ").push_bind(v);
+    }
+    if let Some(v) = filters.bpm_max {
+        qb.push(" AND b.bpm <= ").push_bind(v);
+    }
+    if let Some(v) = filters.od_min {
+        qb.push(" AND b.od >= ").push_bind(v);
+    }
+    if let Some(v) = filters.od_max {
+        qb.push(" AND b.od <= ").push_bind(v);
+    }
+    if !filters.statuses.is_empty() {
+        qb.push(" AND b.status = ANY(")
+            .push_bind(filters.statuses.clone())
+            .push(")");
+    }
+    if let Some(v) = filters.drain_time_min {
+        qb.push(" AND r.drain_time >= ").push_bind(v);
+    }
+    if let Some(v) = filters.drain_time_max {
+        qb.push(" AND r.drain_time <= ").push_bind(v);
+    }
+
+    if filters.rating_min.is_some() || filters.rating_max.is_some() {
+        let rating_types = if filters.rating_types.is_empty() {
+            vec![DEFAULT_RATING_TYPE.to_string()]
+        } else {
+            filters.rating_types.clone()
+        };
+        qb.push(
+            " AND EXISTS (SELECT 1 FROM rating rt WHERE rt.rates_id = r.id AND rt.rating_type = ANY(",
+        )
+        .push_bind(rating_types)
+        .push(")");
+        if let Some(v) = filters.rating_min {
+            qb.push(" AND rt.rating >= ").push_bind(v);
+        }
+        if let Some(v) = filters.rating_max {
+            qb.push(" AND rt.rating <= ").push_bind(v);
+        }
+        qb.push(")");
+    }
+
+    if !filters.skillsets.is_empty() {
+        let joiner = match filters.skillset_match {
+            MatchMode::All => " AND ",
+            MatchMode::Any => " OR ",
+        };
+        qb.push(" AND (");
+        for (i, bound) in filters.skillsets.iter().enumerate() {
+            if i > 0 {
+                qb.push(joiner);
+            }
+            push_skillset_bound(qb, bound);
+        }
+        qb.push(")");
+    }
+}
+
+/// Recherche sur artiste/titre/créateur du beatmapset `bs`
+pub fn push_search_term(qb: &mut QueryBuilder<'_, Postgres>, filters: &ListFilters) {
+    if let Some(term) = filters
+        .search_term
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+    {
+        let pattern = like_pattern(term.trim());
+        qb.push(" AND (bs.artist ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR bs.title ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR bs.creator ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+}
+
+/// Ajoute les conditions de `filters` à une requête `... FROM beatmapset bs WHERE TRUE`.
+/// Un beatmapset correspond si au moins une de ses difficultés satisfait
+/// tous les filtres de difficulté, au rate demandé ou à un rate de la fenêtre.
+fn push_filters(qb: &mut QueryBuilder<'_, Postgres>, filters: &ListFilters) {
+    push_search_term(qb, filters);
+
+    if !has_difficulty_filters(filters) {
+        return;
+    }
+
+    qb.push(
+        " AND EXISTS (SELECT 1 FROM beatmap b JOIN rates r ON r.beatmap_id = b.id \
+         WHERE b.beatmapset_id = bs.id",
+    );
+    push_difficulty_conditions(qb, filters);
+    qb.push(")");
+}
+
+pub async fn count_beatmapsets(pool: &PgPool, filters: &ListFilters) -> Result<i64, sqlx::Error> {
+    let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM beatmapset bs WHERE TRUE");
+    push_filters(&mut qb, filters);
+    qb.build_query_scalar::<i64>().fetch_one(pool).await
+}
+
+/// Clé de tri d'une valeur absente : après toutes les autres, dans les deux sens
+fn missing_sort_value(order: SortOrder) -> f64 {
+    match order {
+        SortOrder::Asc => f64::MAX,
+        SortOrder::Desc => f64::MIN,
+    }
+}
+
+/// Ajoute les colonnes `sort_num` / `sort_text` du tri demandé. Les valeurs
+/// absentes (set sans rate calculé, date inconnue...) sont rangées en fin de liste.
+fn push_sort_columns(qb: &mut QueryBuilder<'_, Postgres>, sort: &Sort) {
+    let missing = missing_sort_value(sort.order);
+
+    qb.push("COALESCE(");
+    match sort.field {
+        SortField::Id => {
+            qb.push("bs.id::float8");
+        }
+        SortField::Rating => {
+            qb.push(
+                "(SELECT MAX(rt.rating) FROM beatmap b \
+                 JOIN rates r ON r.beatmap_id = b.id AND ",
+            );
+            push_rate_scope(qb, sort.rate);
+            qb.push(" JOIN rating rt ON rt.rates_id = r.id AND rt.rating_type = ")
+                .push_bind(sort.rating_type.clone())
+                .push(" WHERE b.beatmapset_id = bs.id)");
+        }
+        SortField::Skillset => {
+            qb.push(
+                "(SELECT MAX(s.pattern) FROM beatmap b \
+                 JOIN rates r ON r.beatmap_id = b.id AND ",
+            );
+            push_rate_scope(qb, sort.rate);
+            qb.push(" JOIN skillset s ON s.rates_id = r.id AND s.pattern_type = ")
+                .push_bind(sort.pattern_type.clone().unwrap_or_default())
+                .push(" WHERE b.beatmapset_id = bs.id)");
+        }
+        SortField::Bpm => {
+            qb.push("(SELECT MAX(b.bpm) FROM beatmap b WHERE b.beatmapset_id = bs.id)");
+        }
+        SortField::Od => {
+            qb.push("(SELECT MAX(b.od) FROM beatmap b WHERE b.beatmapset_id = bs.id)");
+        }
+        SortField::DrainTime => {
+            qb.push(
+                "(SELECT MAX(r.drain_time)::float8 FROM beatmap b \
+                 JOIN rates r ON r.beatmap_id = b.id AND ",
+            );
+            push_rate_scope(qb, sort.rate);
+            qb.push(" WHERE b.beatmapset_id = bs.id)");
+        }
+        SortField::CreatedAt => {
+            qb.push("EXTRACT(EPOCH FROM bs.created_at)::float8");
+        }
+        SortField::RankedDate => {
+            qb.push("EXTRACT(EPOCH FROM bs.ranked_date)::float8");
+        }
+        SortField::Title | SortField::Artist => {
+            qb.push("0::float8");
+        }
+    }
+    qb.push(", ").push_bind(missing).push(") AS sort_num, ");
+
+    qb.push(match sort.field {
+        SortField::Title => "COALESCE(LOWER(bs.title), '')",
+        SortField::Artist => "COALESCE(LOWER(bs.artist), '')",
+        _ => "''",
+    });
+    qb.push(" AS sort_text");
+}
+
+/// Requête d'une page triée par `sort` puis par `id`, à partir d'un offset ou
+/// d'un curseur. Avec un curseur `prev`, l'ordre est inversé.
+fn page_query(
+    filters: &ListFilters,
+    sort: &Sort,
+    start: &PageStart,
+    limit: i64,
+) -> QueryBuilder<'static, Postgres> {
+    let mut qb = QueryBuilder::new("SELECT * FROM (SELECT bs.id, bs.osu_id, ");
+    push_sort_columns(&mut qb, sort);
+    qb.push(" FROM beatmapset bs WHERE TRUE");
+    push_filters(&mut qb, filters);
+    qb.push(") page WHERE TRUE");
+
+    let order = if is_backwards(start) {
+        sort.order.reversed()
+    } else {
+        sort.order
+    };
+
+    if let PageStart::After(cursor) = start {
+        qb.push(match order {
+            SortOrder::Asc => " AND (sort_num, sort_text, id) > (",
+            SortOrder::Desc => " AND (sort_num, sort_text, id) < (",
+        })
+        .push_bind(cursor.sort_num)
+        .push(", ")
+        .push_bind(cursor.sort_text.clone())
+        .push(", ")
+        .push_bind(cursor.id)
+        .push(")");
+    }
+
+    let dir = order.as_sql();
+    qb.push(format!(
+        " ORDER BY sort_num {dir}, sort_text {dir}, id {dir} LIMIT "
+    ))
+    .push_bind(limit);
+    if let PageStart::Offset(offset) = start {
+        qb.push(" OFFSET ").push_bind(*offset);
+    }
+    qb
+}
+
+fn is_backwards(start: &PageStart) -> bool {
+    matches!(start, PageStart::After(c) if c.direction == CursorDirection::Prev)
+}
+
+/// Page de beatmapsets triée par `sort`, départagée par `bs.id`.
+/// Avec un curseur `prev`, les lignes sont lues à rebours puis remises dans l'ordre.
+pub async fn find_beatmapset_page(
+    pool: &PgPool,
+    filters: &ListFilters,
+    sort: &Sort,
+    start: &PageStart,
+    limit: i64,
+) -> Result<Vec<BeatmapsetPageRow>, sqlx::Error> {
+    let mut rows = page_query(filters, sort, start, limit)
+        .build_query_as::<BeatmapsetPageRow>()
+        .fetch_all(pool)
+        .await?;
+    if is_backwards(start) {
+        rows.reverse();
+    }
+    Ok(rows)
+}
+
+/// Tirage sans parcourir toute la table : chaque pivot, tiré entre 0 et le plus
+/// grand id, retient le premier beatmapset correspondant qui le suit
+fn random_query(filters: &ListFilters, limit: i64) -> QueryBuilder<'static, Postgres> {
+    let mut qb = QueryBuilder::new(
+        "SELECT osu_id FROM (SELECT DISTINCT picked.id, picked.osu_id \
+         FROM (SELECT floor(random() * (SELECT MAX(id) FROM beatmapset))::int4 AS pivot \
+         FROM generate_series(1, ",
+    );
+    qb.push_bind(limit * RANDOM_OVERSAMPLE).push(
+        ")) pivots CROSS JOIN LATERAL (SELECT bs.id, bs.osu_id FROM beatmapset bs \
+         WHERE bs.id > pivots.pivot",
+    );
+    push_filters(&mut qb, filters);
+    qb.push(" ORDER BY bs.id LIMIT 1) picked) sample ORDER BY random() LIMIT ")
+        .push_bind(limit);
+    qb
+}
+
+/// Ids osu! de `limit` beatmapsets tirés au hasard parmi ceux qui correspondent
+/// aux filtres. Si les pivots en trouvent trop peu (filtres très sélectifs), le
+/// tirage se fait sur tous les beatmapsets correspondants.
+pub async fn find_random_beatmapset_osu_ids(
+    pool: &PgPool,
+    filters: &ListFilters,
+    limit: i64,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let sampled: Vec<Option<i32>> = random_query(filters, limit)
+        .build_query_scalar()
+        .fetch_all(pool)
+        .await?;
+    if sampled.len() as i64 >= limit {
+        return Ok(sampled.into_iter().flatten().collect());
+    }
+
+    let mut qb = QueryBuilder::new("SELECT bs.osu_id FROM beatmapset bs WHERE TRUE");
+    push_filters(&mut qb, filters);
+    qb.push(" ORDER BY random() LIMIT ").push_bind(limit);
+    let all: Vec<Option<i32>> = qb.build_query_scalar().fetch_all(pool).await?;
+    Ok(all.into_iter().flatten().collect())
+}
+
+/// Beatmapsets de `osu_ids` dans cet ordre, avec leurs difficultés
+pub async fn load_beatmapsets(
+    pool: &PgPool,
+    osu_ids: &[i32],
+    rating_type: Option<String>,
+) -> Result<Vec<Beatmapset>, sqlx::Error> {
+    if osu_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut sets = find_by_osu_ids(pool, osu_ids, rating_type).await?;
+    sets.sort_by_key(|set| {
+        set.osu_id
+            .and_then(|id| osu_ids.iter().position(|&wanted| wanted == id))
+    });
+    Ok(sets)
+}
+
+fn matching_centirates_query(
+    beatmapset_osu_ids: &[i32],
+    filters: &ListFilters,
+) -> QueryBuilder<'static, Postgres> {
+    let mut qb = QueryBuilder::new(
+        "SELECT bs.osu_id, b.osu_id, ARRAY_AGG(r.centirate ORDER BY r.centirate) \
+         FROM beatmapset bs JOIN beatmap b ON b.beatmapset_id = bs.id \
+         JOIN rates r ON r.beatmap_id = b.id WHERE bs.osu_id = ANY(",
+    );
+    qb.push_bind(beatmapset_osu_ids.to_vec()).push(")");
+    push_difficulty_conditions(&mut qb, filters);
+    qb.push(" GROUP BY bs.osu_id, b.osu_id");
+    qb
+}
+
+/// Rates de la fenêtre où chaque difficulté satisfait les filtres :
+/// (id osu! du beatmapset, id osu! de la difficulté, rates)
+pub async fn find_matching_centirates(
+    pool: &PgPool,
+    beatmapset_osu_ids: &[i32],
+    filters: &ListFilters,
+) -> Result<Vec<(i32, i32, Vec<i32>)>, sqlx::Error> {
+    matching_centirates_query(beatmapset_osu_ids, filters)
+        .build_query_as::<(i32, i32, Vec<i32>)>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Regroupe les rates trouvés par beatmapset, puis par difficulté
+fn group_matching(rows: Vec<(i32, i32, Vec<i32>)>) -> HashMap<i32, BTreeMap<i32, Vec<i32>>> {
+    let mut by_set: HashMap<i32, BTreeMap<i32, Vec<i32>>> = HashMap::new();
+    for (set_osu_id, beatmap_osu_id, centirates) in rows {
+        by_set
+            .entry(set_osu_id)
+            .or_default()
+            .insert(beatmap_osu_id, centirates);
+    }
+    by_set
+}
+
+/// Beatmapsets d'une page dans l'ordre de `osu_ids`. Avec une fenêtre de
+/// rates, chacun porte les rates où ses difficultés satisfont les filtres.
+pub async fn load_list_items(
+    pool: &PgPool,
+    osu_ids: &[i32],
+    filters: &ListFilters,
+) -> Result<Vec<BeatmapsetListItem>, sqlx::Error> {
+    let sets = load_beatmapsets(pool, osu_ids, filters.rating_types.first().cloned()).await?;
+    let mut matching = match filters.rate {
+        Some(RateScope::Within { .. }) if !osu_ids.is_empty() => Some(group_matching(
+            find_matching_centirates(pool, osu_ids, filters).await?,
+        )),
+        _ => None,
+    };
+
+    Ok(sets
+        .into_iter()
+        .map(|beatmapset| {
+            let matching_centirates = matching.as_mut().map(|matching| {
+                beatmapset
+                    .osu_id
+                    .and_then(|id| matching.remove(&id))
+                    .unwrap_or_default()
+            });
+            BeatmapsetListItem {
+                beatmapset,
+                matching_centirates,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::beatmapset::types::Cursor;
+
+    fn filters_sql(filters: &ListFilters) -> String {
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT 1 FROM beatmapset bs WHERE TRUE");
+        push_filters(&mut qb, filters);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn no_filter_adds_no_condition() {
+        assert_eq!(
+            filters_sql(&ListFilters::default()),
+            "SELECT 1 FROM beatmapset bs WHERE TRUE"
+        );
+    }
+
+    #[test]
+    fn search_term_alone_skips_the_difficulty_join() {
+        let sql = filters_sql(&ListFilters {
+            search_term: Some("camellia".to_string()),
+            ..Default::default()
+        });
+        assert!(sql.contains("bs.artist ILIKE $1 OR bs.title ILIKE $2 OR bs.creator ILIKE $3"));
+        assert!(!sql.contains("EXISTS"));
+    }
+
+    #[test]
+    fn like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("100%_a\\b"), "%100\\%\\_a\\\\b%");
+    }
+
+    #[test]
+    fn difficulty_filters_apply_to_one_difficulty_at_the_reference_rate() {
+        let sql = filters_sql(&ListFilters {
+            statuses: vec!["ranked".to_string(), "loved".to_string()],
+            bpm_min: Some(120.0),
+            ..Default::default()
+        });
+        assert_eq!(
+            sql,
+            "SELECT 1 FROM beatmapset bs WHERE TRUE AND EXISTS (SELECT 1 FROM beatmap b \
+             JOIN rates r ON r.beatmap_id = b.id WHERE b.beatmapset_id = bs.id \
+             AND r.centirate = $1 AND b.bpm >= $2 AND b.status = ANY($3))"
+        );
+    }
+
+    #[test]
+    fn rating_bounds_accept_any_listed_type() {
+        let sql = filters_sql(&ListFilters {
+            rating_types: vec!["overall".to_string(), "etterna".to_string()],
+            rating_min: Some(20.0),
+            rating_max: Some(24.0),
+            rate: Some(RateScope::At(120)),
+            ..Default::default()
+        });
+        assert!(sql.contains("r.centirate = $1"));
+        assert!(sql.contains(
+            "EXISTS (SELECT 1 FROM rating rt WHERE rt.rates_id = r.id \
+             AND rt.rating_type = ANY($2) AND rt.rating >= $3 AND rt.rating <= $4)"
+        ));
+    }
+
+    #[test]
+    fn skillsets_combine_with_the_match_mode() {
+        let bounds = vec![
+            SkillsetBound {
+                pattern_type: Some("jumpstream".to_string()),
+                min: Some(20.0),
+                max: None,
+            },
+            SkillsetBound {
+                pattern_type: Some("stream".to_string()),
+                min: None,
+                max: Some(24.0),
+            },
+        ];
+        let all = filters_sql(&ListFilters {
+            skillsets: bounds.clone(),
+            ..Default::default()
+        });
+        assert!(all.contains(
+            "(EXISTS (SELECT 1 FROM skillset s WHERE s.rates_id = r.id \
+             AND s.pattern_type = $2 AND s.pattern >= $3) AND EXISTS"
+        ));
+
+        let any = filters_sql(&ListFilters {
+            skillsets: bounds,
+            skillset_match: MatchMode::Any,
+            ..Default::default()
+        });
+        assert!(any.contains("AND s.pattern >= $3) OR EXISTS"));
+    }
+
+    #[test]
+    fn rate_window_matches_any_rate_inside() {
+        let sql = filters_sql(&ListFilters {
+            rate: Some(RateScope::Within { min: 100, max: 150 }),
+            ..Default::default()
+        });
+        assert!(sql.contains("r.centirate BETWEEN $1 AND $2"));
+    }
+
+    fn page_sql(sort: &Sort, start: &PageStart) -> String {
+        page_query(&ListFilters::default(), sort, start, 21)
+            .sql()
+            .to_string()
+    }
+
+    fn cursor(direction: CursorDirection) -> Cursor {
+        Cursor {
+            id: 42,
+            sort_num: 21.5,
+            sort_text: String::new(),
+            sort: Sort::by_id().fingerprint(),
+            direction,
+        }
+    }
+
+    fn rating_sort(order: SortOrder) -> Sort {
+        Sort {
+            field: SortField::Rating,
+            order,
+            ..Sort::by_id()
+        }
+    }
+
+    #[test]
+    fn offset_page_orders_by_key_then_id() {
+        let sql = page_sql(&Sort::by_id(), &PageStart::Offset(40));
+        assert!(sql.starts_with("SELECT * FROM (SELECT bs.id, bs.osu_id, COALESCE(bs.id::float8"));
+        assert!(sql.ends_with(
+            ") page WHERE TRUE ORDER BY sort_num ASC, sort_text ASC, id ASC LIMIT $2 OFFSET $3"
+        ));
+    }
+
+    #[test]
+    fn next_cursor_continues_after_the_key() {
+        let sql = page_sql(
+            &rating_sort(SortOrder::Desc),
+            &PageStart::After(cursor(CursorDirection::Next)),
+        );
+        assert!(sql.contains(" AND (sort_num, sort_text, id) < ($"));
+        assert!(sql.contains("ORDER BY sort_num DESC, sort_text DESC, id DESC LIMIT"));
+        assert!(!sql.contains("OFFSET"));
+    }
+
+    #[test]
+    fn prev_cursor_reads_backwards() {
+        let sql = page_sql(
+            &rating_sort(SortOrder::Desc),
+            &PageStart::After(cursor(CursorDirection::Prev)),
+        );
+        assert!(sql.contains(" AND (sort_num, sort_text, id) > ($"));
+        assert!(sql.contains("ORDER BY sort_num ASC, sort_text ASC, id ASC LIMIT"));
+        assert!(is_backwards(&PageStart::After(cursor(
+            CursorDirection::Prev
+        ))));
+        assert!(!is_backwards(&PageStart::After(cursor(
+            CursorDirection::Next
+        ))));
+        assert!(!is_backwards(&PageStart::Offset(0)));
+    }
+
+    #[test]
+    fn page_applies_the_filters_inside() {
+        let filters = ListFilters {
+            od_min: Some(8.0),
+            ..Default::default()
+        };
+        let sql = page_query(&filters, &Sort::by_id(), &PageStart::Offset(0), 21)
+            .sql()
+            .to_string();
+        assert!(sql.contains("FROM beatmapset bs WHERE TRUE AND EXISTS (SELECT 1 FROM beatmap b"));
+        assert!(
+            sql.contains("AND b.od >= $3) ) page WHERE TRUE")
+                || sql.contains("AND b.od >= $3)) page WHERE TRUE")
+        );
+    }
+
+    fn sort_sql(sort: &Sort) -> String {
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT ");
+        push_sort_columns(&mut qb, sort);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn rating_sort_takes_the_best_difficulty_at_the_rate() {
+        let sort = Sort {
+            rate: Some(RateScope::At(120)),
+            ..rating_sort(SortOrder::Desc)
+        };
+        assert_eq!(
+            sort_sql(&sort),
+            "SELECT COALESCE((SELECT MAX(rt.rating) FROM beatmap b \
+             JOIN rates r ON r.beatmap_id = b.id AND r.centirate = $1 \
+             JOIN rating rt ON rt.rates_id = r.id AND rt.rating_type = $2 \
+             WHERE b.beatmapset_id = bs.id), $3) AS sort_num, '' AS sort_text"
+        );
+    }
+
+    #[test]
+    fn skillset_sort_uses_the_rate_window() {
+        let sort = Sort {
+            field: SortField::Skillset,
+            pattern_type: Some("stream".to_string()),
+            rate: Some(RateScope::Within { min: 100, max: 150 }),
+            ..rating_sort(SortOrder::Asc)
+        };
+        let sql = sort_sql(&sort);
+        assert!(sql.contains("r.centirate BETWEEN $1 AND $2"));
+        assert!(sql.contains("JOIN skillset s ON s.rates_id = r.id AND s.pattern_type = $3"));
+    }
+
+    #[test]
+    fn text_sorts_use_lowercase_text() {
+        let sort = Sort {
+            field: SortField::Artist,
+            ..rating_sort(SortOrder::Asc)
+        };
+        assert!(
+            sort_sql(&sort).ends_with("AS sort_num, COALESCE(LOWER(bs.artist), '') AS sort_text")
+        );
+    }
+
+    #[test]
+    fn missing_values_sort_last() {
+        assert_eq!(missing_sort_value(SortOrder::Asc), f64::MAX);
+        assert_eq!(missing_sort_value(SortOrder::Desc), f64::MIN);
+    }
+
+    #[test]
+    fn matching_centirates_are_grouped_per_difficulty() {
+        let filters = ListFilters {
+            rating_min: Some(24.0),
+            rate: Some(RateScope::Within { min: 100, max: 150 }),
+            ..Default::default()
+        };
+        let sql = matching_centirates_query(&[1000, 2000], &filters)
+            .sql()
+            .to_string();
+        assert!(sql.contains("WHERE bs.osu_id = ANY($1) AND r.centirate BETWEEN $2 AND $3"));
+        assert!(sql.contains("AND rt.rating >= $5)"));
+        assert!(sql.ends_with(" GROUP BY bs.osu_id, b.osu_id"));
+
+        let grouped = group_matching(vec![
+            (1000, 11, vec![100, 110]),
+            (1000, 12, vec![150]),
+            (2000, 21, vec![120]),
+        ]);
+        assert_eq!(
+            grouped[&1000],
+            BTreeMap::from([(11, vec![100, 110]), (12, vec![150])])
+        );
+        assert_eq!(grouped[&2000], BTreeMap::from([(21, vec![120])]));
+    }
+
+    #[test]
+    fn random_samples_from_pivots_then_shuffles() {
+        let sql = random_query(
+            &ListFilters {
+                statuses: vec!["ranked".to_string()],
+                ..Default::default()
+            },
+            9,
+        )
+        .sql()
+        .to_string();
+        assert!(sql.contains("generate_series(1, $1)"));
+        assert!(sql.contains("WHERE bs.id > pivots.pivot AND EXISTS"));
+        assert!(sql.ends_with("ORDER BY bs.id LIMIT 1) picked) sample ORDER BY random() LIMIT $4"));
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::BTreeMap;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use dto::models::beatmaps::simple::types::Beatmapset;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::query::DEFAULT_RATING_TYPE;
+
+/// Valeurs de `rating.rating_type` produites par le calculateur
+pub const RATING_TYPES: [&str; 4] = ["overall", "etterna", "osu", "sunny"];
+
+/// Valeurs de `skillset.pattern_type` (skillsets MSD)
+pub const PATTERN_TYPES: [&str; 7] = [
+    "stream",
+    "jumpstream",
+    "handstream",
+    "stamina",
+    "jackspeed",
+    "chordjack",
+    "technical",
+];
+
+/// Statuts osu! d'une beatmap
+pub const BEATMAP_STATUSES: [&str; 7] = [
+    "graveyard",
+    "wip",
+    "pending",
+    "ranked",
+    "approved",
+    "qualified",
+    "loved",
+];
+
+/// Bornes de l'Overall Difficulty
+pub const OD_RANGE: (f64, f64) = (0.0, 10.0);
+
+/// Rates calculés, de 0.5x à 2.0x
+pub const CENTIRATE_RANGE: (i32, i32) = (50, 200);
+
+/// Rate(s) auxquels s'appliquent les filtres rating/skillset/drain time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateScope {
+    /// Un seul rate
+    At(i32),
+    /// N'importe quel rate de la fenêtre
+    Within { min: i32, max: i32 },
+}
+
+/// Combinaison de plusieurs bornes de skillset sur une même difficulté
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// Toutes les bornes doivent être respectées (AND)
+    #[default]
+    All,
+    /// Au moins une borne doit être respectée (OR)
+    Any,
+}
+
+impl MatchMode {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "all" => Some(Self::All),
+            "any" => Some(Self::Any),
+            _ => None,
+        }
+    }
+}
+
+/// Borne sur un skillset ; sans `pattern_type`, s'applique à n'importe lequel
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillsetBound {
+    pub pattern_type: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// Filtres de liste. Les statuts, types de rating et skillsets acceptent
+/// plusieurs valeurs : les valeurs d'un même champ sont combinées en OR, les
+/// champs entre eux en AND.
+#[derive(Debug, Clone, Default)]
+pub struct ListFilters {
+    pub search_term: Option<String>,
+    /// Rating dans l'intervalle pour au moins un de ces types
+    pub rating_types: Vec<String>,
+    pub rating_min: Option<f64>,
+    pub rating_max: Option<f64>,
+    pub skillsets: Vec<SkillsetBound>,
+    pub skillset_match: MatchMode,
+    /// Un des statuts listés
+    pub statuses: Vec<String>,
+    pub total_time_min: Option<i32>,
+    pub total_time_max: Option<i32>,
+    pub bpm_min: Option<f64>,
+    pub bpm_max: Option<f64>,
+    pub od_min: Option<f64>,
+    pub od_max: Option<f64>,
+    pub drain_time_min: Option<i32>,
+    pub drain_time_max: Option<i32>,
+    /// Sans valeur, le rate de référence (1.0x)
+    pub rate: Option<RateScope>,
+}
+
+impl ListFilters {
+    /// Premier type de rating demandé, sinon celui par défaut
+    pub fn primary_rating_type(&self) -> &str {
+        self.rating_types
+            .first()
+            .map_or(DEFAULT_RATING_TYPE, String::as_str)
+    }
+
+    /// Premier skillset nommé, celui que suit un tri par skillset
+    pub fn primary_pattern_type(&self) -> Option<&str> {
+        self.skillsets
+            .iter()
+            .find_map(|s| s.pattern_type.as_deref())
+    }
+}
+
+/// Champs autorisés pour `sort`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    /// Ordre d'insertion, utilisé sans `sort`
+    Id,
+    Rating,
+    Skillset,
+    Bpm,
+    DrainTime,
+    Od,
+    CreatedAt,
+    RankedDate,
+    Title,
+    Artist,
+}
+
+impl SortField {
+    pub const ALLOWED: [&'static str; 9] = [
+        "rating",
+        "skillset",
+        "bpm",
+        "drain_time",
+        "od",
+        "created_at",
+        "ranked_date",
+        "title",
+        "artist",
+    ];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "rating" => Some(Self::Rating),
+            "skillset" => Some(Self::Skillset),
+            "bpm" => Some(Self::Bpm),
+            "drain_time" => Some(Self::DrainTime),
+            "od" => Some(Self::Od),
+            "created_at" => Some(Self::CreatedAt),
+            "ranked_date" => Some(Self::RankedDate),
+            "title" => Some(Self::Title),
+            "artist" => Some(Self::Artist),
+            _ => None,
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Title | Self::Artist)
+    }
+
+    /// Ordre par défaut : alphabétique pour le texte, décroissant sinon
+    pub fn default_order(&self) -> SortOrder {
+        if self.is_text() {
+            SortOrder::Asc
+        } else {
+            SortOrder::Desc
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+
+    pub fn reversed(&self) -> Self {
+        match self {
+            Self::Asc => Self::Desc,
+            Self::Desc => Self::Asc,
+        }
+    }
+
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+/// Tri demandé ; `rating`/`skillset` portent sur le type et le rate choisis dans les filtres
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sort {
+    pub field: SortField,
+    pub order: SortOrder,
+    pub rating_type: String,
+    pub pattern_type: Option<String>,
+    /// Rate(s) des valeurs triées ; sur une fenêtre, la meilleure valeur
+    pub rate: Option<RateScope>,
+}
+
+impl Sort {
+    /// Tri par défaut : ordre d'insertion
+    pub fn by_id() -> Self {
+        Self {
+            field: SortField::Id,
+            order: SortOrder::Asc,
+            rating_type: DEFAULT_RATING_TYPE.to_string(),
+            pattern_type: None,
+            rate: None,
+        }
+    }
+
+    /// Identifie le tri dans un curseur, pour refuser un curseur réutilisé avec un autre tri
+    pub fn fingerprint(&self) -> String {
+        let rate = match self.rate {
+            None => String::new(),
+            Some(RateScope::At(centirate)) => centirate.to_string(),
+            Some(RateScope::Within { min, max }) => format!("{}-{}", min, max),
+        };
+        format!(
+            "{:?}:{:?}:{}:{}:{}",
+            self.field,
+            self.order,
+            self.rating_type,
+            self.pattern_type.as_deref().unwrap_or_default(),
+            rate
+        )
+    }
+}
+
+/// Position dans une liste triée, encodée en base64 dans `next_cursor`/`prev_cursor`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Id du dernier (ou premier, pour `prev`) élément vu
+    pub id: i32,
+    pub sort_num: f64,
+    pub sort_text: String,
+    pub sort: String,
+    pub direction: CursorDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorDirection {
+    Next,
+    Prev,
+}
+
+impl Cursor {
+    pub fn at(row: &BeatmapsetPageRow, sort: &Sort, direction: CursorDirection) -> Self {
+        Self {
+            id: row.id,
+            sort_num: row.sort_num,
+            sort_text: row.sort_text.clone(),
+            sort: sort.fingerprint(),
+            direction,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let json = URL_SAFE_NO_PAD.decode(raw).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+/// Où commence la page demandée
+#[derive(Debug, Clone)]
+pub enum PageStart {
+    Offset(i64),
+    After(Cursor),
+}
+
+/// Ligne de page : le beatmapset et sa clé de tri
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BeatmapsetPageRow {
+    pub id: i32,
+    pub osu_id: Option<i32>,
+    pub sort_num: f64,
+    pub sort_text: String,
+}
+
+/// Beatmapset d'une liste
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BeatmapsetListItem {
+    #[serde(flatten)]
+    pub beatmapset: Beatmapset,
+    /// Avec `rates[centirate_min]`/`rates[centirate_max]` : pour chaque difficulté
+    /// (id osu!) qui satisfait les filtres, les rates de la fenêtre où elle le fait
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matching_centirates: Option<BTreeMap<i32, Vec<i32>>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CursorPagination {
+    pub per_page: u32,
+    /// Absent avec `include_total=false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+/// Réponse de `GET /api/beatmapsets` en mode curseur
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BeatmapsetCursorPage {
+    pub message: String,
+    pub status: String,
+    pub data: Vec<BeatmapsetListItem>,
+    pub pagination: CursorPagination,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sort() -> Sort {
+        Sort {
+            field: SortField::Rating,
+            order: SortOrder::Desc,
+            rating_type: "overall".to_string(),
+            pattern_type: None,
+            rate: Some(RateScope::Within { min: 100, max: 150 }),
+        }
+    }
+
+    fn cursor(direction: CursorDirection) -> Cursor {
+        Cursor {
+            id: 42,
+            sort_num: 21.5,
+            sort_text: "camellia".to_string(),
+            sort: sort().fingerprint(),
+            direction,
+        }
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = cursor(CursorDirection::Next);
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn encoded_cursor_is_url_safe() {
+        let encoded = cursor(CursorDirection::Prev).encode();
+        assert!(
+            encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+    }
+
+    #[test]
+    fn malformed_cursor_is_rejected() {
+        assert_eq!(Cursor::decode("not a cursor"), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode(b"{\"id\":1}")), None);
+    }
+
+    #[test]
+    fn cursor_is_taken_at_the_row() {
+        let row = BeatmapsetPageRow {
+            id: 42,
+            osu_id: Some(1000),
+            sort_num: 21.5,
+            sort_text: "camellia".to_string(),
+        };
+        assert_eq!(
+            Cursor::at(&row, &sort(), CursorDirection::Prev),
+            cursor(CursorDirection::Prev)
+        );
+    }
+
+    #[test]
+    fn fingerprint_changes_with_the_sort() {
+        let base = sort().fingerprint();
+        let mut other = sort();
+        other.order = SortOrder::Asc;
+        assert_ne!(other.fingerprint(), base);
+        let mut other = sort();
+        other.rate = Some(RateScope::At(100));
+        assert_ne!(other.fingerprint(), base);
+        let mut other = sort();
+        other.rating_type = "etterna".to_string();
+        assert_ne!(other.fingerprint(), base);
+    }
+
+    #[test]
+    fn text_fields_sort_ascending_by_default() {
+        assert_eq!(SortField::Title.default_order(), SortOrder::Asc);
+        assert_eq!(SortField::Rating.default_order(), SortOrder::Desc);
+        assert!(
+            SortField::ALLOWED
+                .iter()
+                .all(|raw| SortField::parse(raw).is_some())
+        );
+        assert_eq!(SortField::parse("id"), None);
+    }
+}
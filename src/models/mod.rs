@@ -0,0 +1,25 @@
+//! # Models Module
+//!
+//! Types et requêtes SQL propres à l'API, en complément de `dto::models`.
+//! Même découpage que dans dto-lib : `types.rs` pour les structures,
+//! `query.rs` pour les requêtes.
+
+use sqlx::PgPool;
+
+pub mod api_key;
+pub mod beatmap;
+pub mod beatmapset;
+pub mod pending_beatmap;
+pub mod player;
+pub mod rate;
+pub mod recommendation;
+pub mod score;
+pub mod weekly;
+
+/// Applique les migrations de `migrations/`. Celles de database-lib partagent
+/// la table `_sqlx_migrations`, d'où `ignore_missing`.
+pub async fn migrate(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    let mut migrator = sqlx::migrate!();
+    migrator.set_ignore_missing(true);
+    migrator.run(pool).await
+}
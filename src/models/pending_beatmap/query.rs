@@ -0,0 +1,112 @@
+use sqlx::PgPool;
+
+use super::types::{ChecksumStateRow, HashProgressRow};
+
+/// Pour chaque hash : présent dans la file d'attente et/ou déjà traité.
+/// Les hashes inconnus ne sont pas retournés.
+pub async fn find_checksum_states(
+    pool: &PgPool,
+    hashes: &[String],
+) -> Result<Vec<ChecksumStateRow>, sqlx::Error> {
+    sqlx::query_as::<_, ChecksumStateRow>(
+        r#"
+        SELECT h.osu_hash,
+               EXISTS (
+                   SELECT 1 FROM pending_beatmap pb WHERE pb.osu_hash = h.osu_hash
+               ) AS pending,
+               b.osu_id AS beatmap_osu_id
+        FROM UNNEST($1::text[]) AS h(osu_hash)
+        LEFT JOIN beatmap b ON b.file_md5 = h.osu_hash
+        WHERE b.id IS NOT NULL
+           OR EXISTS (SELECT 1 FROM pending_beatmap pb WHERE pb.osu_hash = h.osu_hash)
+        "#,
+    )
+    .bind(hashes)
+    .fetch_all(pool)
+    .await
+}
+
+/// Position de chaque entrée en attente (1 = prochaine traitée), calculée en
+/// une passe sur la file ; à joindre sur `queue.id = pb.id`
+const QUEUE_CTE: &str = r#"
+    WITH queue AS (
+        SELECT id, ROW_NUMBER() OVER (ORDER BY id) AS position
+        FROM pending_beatmap
+        WHERE processing_started_at IS NULL
+          AND processed_at IS NULL
+          AND failed_at IS NULL
+    )
+"#;
+
+/// Colonnes communes de suivi ; attend les alias `pb` (dernière entrée de la
+/// file), `queue` (voir `QUEUE_CTE`), `b` (beatmap) et `bs` (beatmapset).
+const PROGRESS_COLUMNS: &str = r#"
+    pb.id AS pending_id,
+    pb.created_at,
+    pb.processing_started_at,
+    pb.processed_at,
+    pb.failed_at,
+    pb.failure_reason,
+    queue.position AS queue_position,
+    b.id IS NOT NULL AS processed,
+    COALESCE(b.osu_id, pb.osu_id) AS beatmap_osu_id,
+    bs.osu_id AS beatmapset_osu_id
+"#;
+
+/// Progression de chaque hash : dernière entrée de la file, position
+/// parmi les entrées en attente, et beatmap produite le cas échéant.
+/// Un hash absent de la base n'est pas retourné.
+pub async fn find_progress_by_hashes(
+    pool: &PgPool,
+    hashes: &[String],
+) -> Result<Vec<HashProgressRow>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        {QUEUE_CTE}
+        SELECT h.osu_hash, {PROGRESS_COLUMNS}
+        FROM UNNEST($1::text[]) AS h(osu_hash)
+        LEFT JOIN LATERAL (
+            SELECT * FROM pending_beatmap p
+            WHERE p.osu_hash = h.osu_hash
+            ORDER BY p.id DESC
+            LIMIT 1
+        ) pb ON TRUE
+        LEFT JOIN queue ON queue.id = pb.id
+        LEFT JOIN beatmap b ON b.file_md5 = h.osu_hash
+        LEFT JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE pb.id IS NOT NULL OR b.id IS NOT NULL
+        "#
+    );
+    sqlx::query_as::<_, HashProgressRow>(&sql)
+        .bind(hashes)
+        .fetch_all(pool)
+        .await
+}
+
+/// Même chose à partir d'ids de beatmap osu!
+pub async fn find_progress_by_osu_ids(
+    pool: &PgPool,
+    osu_ids: &[i32],
+) -> Result<Vec<HashProgressRow>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        {QUEUE_CTE}
+        SELECT COALESCE(pb.osu_hash, b.file_md5, '') AS osu_hash, {PROGRESS_COLUMNS}
+        FROM UNNEST($1::int[]) AS i(osu_id)
+        LEFT JOIN LATERAL (
+            SELECT * FROM pending_beatmap p
+            WHERE p.osu_id = i.osu_id
+            ORDER BY p.id DESC
+            LIMIT 1
+        ) pb ON TRUE
+        LEFT JOIN queue ON queue.id = pb.id
+        LEFT JOIN beatmap b ON b.osu_id = i.osu_id
+        LEFT JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE pb.id IS NOT NULL OR b.id IS NOT NULL
+        "#
+    );
+    sqlx::query_as::<_, HashProgressRow>(&sql)
+        .bind(osu_ids)
+        .fetch_all(pool)
+        .await
+}
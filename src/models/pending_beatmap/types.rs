@@ -0,0 +1,158 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// État d'un checksum déjà connu de la base
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ChecksumStateRow {
+    pub osu_hash: String,
+    pub pending: bool,
+    pub beatmap_osu_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Queued,
+    AlreadyPending,
+    AlreadyProcessed,
+    /// Déjà présent plus tôt dans la même requête
+    Duplicate,
+    InvalidFormat,
+    RejectedBatchLimit,
+}
+
+/// Résultat pour un checksum soumis à `POST /api/beatmaps/imports`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportResultDto {
+    pub checksum: String,
+    pub status: ImportStatus,
+    /// Renseigné quand `status` vaut `already_processed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beatmap_osu_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportReportDto {
+    /// Lignes réellement ajoutées à la file par cette requête ; un checksum
+    /// ajouté au même moment par un autre import reste signalé `queued`
+    pub queued: usize,
+    pub results: Vec<ImportResultDto>,
+}
+
+/// Ligne brute de suivi d'un hash : file d'attente + beatmap éventuelle
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct HashProgressRow {
+    pub osu_hash: String,
+    pub pending_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+    pub processing_started_at: Option<NaiveDateTime>,
+    pub processed_at: Option<NaiveDateTime>,
+    pub failed_at: Option<NaiveDateTime>,
+    pub failure_reason: Option<String>,
+    pub queue_position: Option<i64>,
+    /// Une beatmap a été produite pour ce hash
+    pub processed: bool,
+    pub beatmap_osu_id: Option<i32>,
+    pub beatmapset_osu_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingState {
+    Queued,
+    Processing,
+    Done,
+    Failed,
+    Unknown,
+}
+
+/// Statut de traitement d'un hash
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct HashStatusDto {
+    /// Vide si l'entrée a été demandée par id et n'est pas encore connue
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub osu_hash: String,
+    pub state: ProcessingState,
+    /// Position dans la file (1 = prochain traité), si `queued`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beatmap_osu_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beatmapset_osu_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    /// Date du dernier changement d'état connu
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl ProcessingState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Processing => "processing",
+            Self::Done => "done",
+            Self::Failed => "failed",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// En file ou en cours de traitement : un changement est attendu
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Queued | Self::Processing)
+    }
+}
+
+impl HashStatusDto {
+    pub fn unknown(osu_hash: String, beatmap_osu_id: Option<i32>) -> Self {
+        Self {
+            osu_hash,
+            state: ProcessingState::Unknown,
+            queue_position: None,
+            beatmap_osu_id,
+            beatmapset_osu_id: None,
+            failure_reason: None,
+            updated_at: None,
+        }
+    }
+}
+
+impl From<HashProgressRow> for HashStatusDto {
+    fn from(row: HashProgressRow) -> Self {
+        let (state, updated_at) = if row.processed || row.processed_at.is_some() {
+            (ProcessingState::Done, row.processed_at)
+        } else if row.failed_at.is_some() {
+            (ProcessingState::Failed, row.failed_at)
+        } else if row.processing_started_at.is_some() {
+            (ProcessingState::Processing, row.processing_started_at)
+        } else if row.pending_id.is_some() {
+            (ProcessingState::Queued, row.created_at)
+        } else {
+            (ProcessingState::Unknown, None)
+        };
+
+        Self {
+            osu_hash: row.osu_hash,
+            state,
+            queue_position: match state {
+                ProcessingState::Queued => row.queue_position,
+                _ => None,
+            },
+            beatmap_osu_id: row.beatmap_osu_id,
+            beatmapset_osu_id: row.beatmapset_osu_id,
+            failure_reason: match state {
+                ProcessingState::Failed => row.failure_reason,
+                _ => None,
+            },
+            updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StatusBatchRequestDto {
+    /// MD5 des fichiers `.osu`, tels qu'envoyés à `/api/beatmaps/imports`
+    pub hashes: Vec<String>,
+}
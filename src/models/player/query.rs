@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use super::types::{Play, PlayRateRow, ProfiledPlay};
+use crate::models::rate::query::find_skillsets;
+
+/// Parties les plus récentes d'un joueur parmi les scores enregistrés
+pub async fn find_recent_plays(
+    pool: &PgPool,
+    player: &str,
+    limit: i64,
+) -> Result<Vec<Play>, sqlx::Error> {
+    sqlx::query_as::<_, Play>(
+        r#"
+        SELECT b.osu_id AS beatmap_osu_id, r.centirate, sc.accuracy, sc.played_at
+        FROM score sc
+        JOIN rates r ON r.id = sc.rates_id
+        JOIN beatmap b ON b.id = r.beatmap_id
+        WHERE sc.player = $1 AND b.osu_id IS NOT NULL
+        ORDER BY sc.played_at DESC, sc.id DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(player)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Rate stocké (et son rating du type donné) de chaque partie ; les parties
+/// sans rate stocké ne sont pas retournées
+pub async fn find_play_rates(
+    pool: &PgPool,
+    plays: &[Play],
+    rating_type: &str,
+) -> Result<Vec<PlayRateRow>, sqlx::Error> {
+    let osu_ids: Vec<i32> = plays.iter().map(|p| p.beatmap_osu_id).collect();
+    let centirates: Vec<i32> = plays.iter().map(|p| p.centirate).collect();
+    sqlx::query_as::<_, PlayRateRow>(
+        r#"
+        SELECT p.idx, r.id AS rates_id, rt.rating
+        FROM UNNEST($1::int4[], $2::int4[]) WITH ORDINALITY AS p(osu_id, centirate, idx)
+        JOIN beatmap b ON b.osu_id = p.osu_id
+        JOIN rates r ON r.beatmap_id = b.id AND r.centirate = p.centirate
+        LEFT JOIN rating rt ON rt.rates_id = r.id AND rt.rating_type = $3
+        "#,
+    )
+    .bind(osu_ids)
+    .bind(centirates)
+    .bind(rating_type)
+    .fetch_all(pool)
+    .await
+}
+
+/// Parties avec le rating et les skillsets de leur rate ; celles dont le
+/// rate n'est pas stocké sont écartées
+pub async fn profile_plays(
+    pool: &PgPool,
+    plays: Vec<Play>,
+    rating_type: &str,
+) -> Result<Vec<ProfiledPlay>, sqlx::Error> {
+    let rows = find_play_rates(pool, &plays, rating_type).await?;
+    let rates_ids: Vec<i32> = rows.iter().map(|r| r.rates_id).collect();
+    let mut skillsets: HashMap<i32, HashMap<String, f64>> = HashMap::new();
+    for entry in find_skillsets(pool, &rates_ids).await? {
+        skillsets
+            .entry(entry.rates_id)
+            .or_default()
+            .insert(entry.pattern_type, entry.pattern);
+    }
+
+    let mut plays: Vec<Option<Play>> = plays.into_iter().map(Some).collect();
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let play = plays.get_mut(row.idx as usize - 1)?.take()?;
+            Some(ProfiledPlay {
+                play,
+                rating: row.rating,
+                skillsets: skillsets.get(&row.rates_id).cloned().unwrap_or_default(),
+            })
+        })
+        .collect())
+}
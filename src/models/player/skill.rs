@@ -0,0 +1,243 @@
+//! Estimation du niveau d'un joueur à partir de ses parties.
+//!
+//! Chaque partie pèse selon sa précision, son rate et son ancienneté. Pour
+//! chaque dimension (rating global, puis chaque skillset), les parties sont
+//! classées par valeur × poids ; le niveau est la moyenne des valeurs des
+//! meilleures, pondérée par leur poids et par une décroissance géométrique
+//! de rang, comme un classement de performances.
+
+use chrono::NaiveDateTime;
+
+use super::types::{Play, PlayContributionDto, PlayerSkillDto, ProfiledPlay, SkillsetLevelDto};
+use crate::models::beatmapset::types::PATTERN_TYPES;
+
+/// Nombre maximal de parties prises en compte par requête
+pub const MAX_PLAYS: usize = 200;
+
+/// En dessous de cette précision, une partie ne compte pas
+const MIN_ACCURACY: f64 = 80.0;
+/// À partir de cette précision, une partie compte pleinement
+const FULL_ACCURACY: f64 = 96.0;
+/// Demi-vie du poids d'une partie, en jours
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+/// Poids minimal d'une partie jouée loin de 1.0x
+const MIN_RATE_FACTOR: f64 = 0.5;
+/// Parties retenues par dimension
+const TOP_PLAYS: usize = 20;
+/// Décroissance du poids d'une partie selon son rang
+const RANK_DECAY: f64 = 0.95;
+/// Parties listées dans `top_plays`
+const TOP_PLAYS_REPORTED: usize = 10;
+
+/// Poids d'une partie, de 0 à 1
+pub fn play_weight(play: &Play, now: NaiveDateTime) -> f64 {
+    let accuracy =
+        ((play.accuracy - MIN_ACCURACY) / (FULL_ACCURACY - MIN_ACCURACY)).clamp(0.0, 1.0);
+    let rate = (1.0 - f64::from((play.centirate - 100).abs()) / 200.0).max(MIN_RATE_FACTOR);
+    let recency = play.played_at.map_or(1.0, |at| {
+        let age_days = (now - at).num_seconds().max(0) as f64 / 86_400.0;
+        0.5_f64.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+    });
+    accuracy * rate * recency
+}
+
+/// Niveau sur une dimension et part de chaque partie retenue (index dans `values`)
+fn level(values: &[(usize, f64, f64)]) -> Option<(f64, Vec<(usize, f64)>)> {
+    let mut ranked: Vec<&(usize, f64, f64)> = values.iter().filter(|(_, _, w)| *w > 0.0).collect();
+    ranked.sort_by(|a, b| (b.1 * b.2).total_cmp(&(a.1 * a.2)));
+    ranked.truncate(TOP_PLAYS);
+    if ranked.is_empty() {
+        return None;
+    }
+
+    let mut weights = 0.0;
+    let mut weighted = 0.0;
+    let mut parts = Vec::with_capacity(ranked.len());
+    for (rank, &&(index, value, weight)) in ranked.iter().enumerate() {
+        let k = weight * RANK_DECAY.powi(rank as i32);
+        weights += k;
+        weighted += k * value;
+        parts.push((index, k * value));
+    }
+    let shares = parts
+        .into_iter()
+        .map(|(index, part)| (index, if weighted > 0.0 { part / weighted } else { 0.0 }))
+        .collect();
+    Some((weighted / weights, shares))
+}
+
+pub fn estimate(
+    plays: &[ProfiledPlay],
+    submitted: usize,
+    rating_type: &str,
+    now: NaiveDateTime,
+) -> PlayerSkillDto {
+    let weights: Vec<f64> = plays.iter().map(|p| play_weight(&p.play, now)).collect();
+    let plays_used = weights.iter().filter(|&&w| w > 0.0).count();
+
+    let ratings: Vec<(usize, f64, f64)> = plays
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| Some((i, p.rating?, weights[i])))
+        .collect();
+    let (overall, top_plays) = match level(&ratings) {
+        Some((overall, shares)) => {
+            let top_plays = shares
+                .into_iter()
+                .take(TOP_PLAYS_REPORTED)
+                .map(|(i, share)| {
+                    let play = &plays[i];
+                    PlayContributionDto {
+                        beatmap_osu_id: play.play.beatmap_osu_id,
+                        centirate: play.play.centirate,
+                        accuracy: play.play.accuracy,
+                        played_at: play.play.played_at,
+                        rating: play.rating.unwrap_or_default(),
+                        weight: weights[i],
+                        share,
+                    }
+                })
+                .collect();
+            (Some(overall), top_plays)
+        }
+        None => (None, Vec::new()),
+    };
+
+    let skillsets = PATTERN_TYPES
+        .iter()
+        .filter_map(|&pattern_type| {
+            let values: Vec<(usize, f64, f64)> = plays
+                .iter()
+                .enumerate()
+                .filter_map(|(i, p)| Some((i, *p.skillsets.get(pattern_type)?, weights[i])))
+                .collect();
+            let (value, _) = level(&values)?;
+            Some(SkillsetLevelDto {
+                pattern_type: pattern_type.to_string(),
+                value,
+            })
+        })
+        .collect();
+
+    PlayerSkillDto {
+        rating_type: rating_type.to_string(),
+        overall,
+        skillsets,
+        plays_used,
+        plays_ignored: submitted - plays_used,
+        top_plays,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{Days, NaiveDate};
+
+    use super::*;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 1)
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .unwrap()
+    }
+
+    fn play(centirate: i32, accuracy: f64, days_ago: Option<u64>) -> Play {
+        Play {
+            beatmap_osu_id: 1,
+            centirate,
+            accuracy,
+            played_at: days_ago.map(|d| now() - Days::new(d)),
+        }
+    }
+
+    fn profiled(rating: f64, accuracy: f64) -> ProfiledPlay {
+        ProfiledPlay {
+            play: play(100, accuracy, None),
+            rating: Some(rating),
+            skillsets: HashMap::from([("stream".to_string(), rating / 2.0)]),
+        }
+    }
+
+    #[test]
+    fn accuracy_factor_is_clamped() {
+        assert_eq!(play_weight(&play(100, 70.0, None), now()), 0.0);
+        assert_eq!(play_weight(&play(100, MIN_ACCURACY, None), now()), 0.0);
+        assert_eq!(play_weight(&play(100, 88.0, None), now()), 0.5);
+        assert_eq!(play_weight(&play(100, FULL_ACCURACY, None), now()), 1.0);
+        assert_eq!(play_weight(&play(100, 100.0, None), now()), 1.0);
+    }
+
+    #[test]
+    fn rate_factor_never_drops_below_its_floor() {
+        assert_eq!(play_weight(&play(150, 100.0, None), now()), 0.75);
+        assert_eq!(play_weight(&play(50, 100.0, None), now()), 0.75);
+        assert_eq!(play_weight(&play(200, 100.0, None), now()), MIN_RATE_FACTOR);
+        assert_eq!(play_weight(&play(300, 100.0, None), now()), MIN_RATE_FACTOR);
+    }
+
+    #[test]
+    fn weight_halves_every_half_life() {
+        let days = RECENCY_HALF_LIFE_DAYS as u64;
+        assert_eq!(play_weight(&play(100, 100.0, Some(0)), now()), 1.0);
+        assert!((play_weight(&play(100, 100.0, Some(days)), now()) - 0.5).abs() < 1e-9);
+        assert!((play_weight(&play(100, 100.0, Some(2 * days)), now()) - 0.25).abs() < 1e-9);
+        // Une date future ne donne pas plus que le poids maximal
+        let future = Play {
+            played_at: Some(now() + Days::new(3)),
+            ..play(100, 100.0, None)
+        };
+        assert_eq!(play_weight(&future, now()), 1.0);
+    }
+
+    #[test]
+    fn level_decays_by_rank() {
+        let (value, shares) = level(&[(0, 30.0, 1.0), (1, 10.0, 1.0)]).unwrap();
+        let expected = (30.0 + RANK_DECAY * 10.0) / (1.0 + RANK_DECAY);
+        assert!((value - expected).abs() < 1e-9);
+        assert_eq!(shares[0].0, 0);
+        assert!((shares[0].1 - 30.0 / (30.0 + RANK_DECAY * 10.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn level_ranks_by_weighted_value_and_keeps_top_plays() {
+        let values: Vec<(usize, f64, f64)> = (0..TOP_PLAYS + 5)
+            .map(|i| (i, i as f64, 1.0))
+            .chain([(99, 1000.0, 0.0)])
+            .collect();
+        let (_, shares) = level(&values).unwrap();
+        assert_eq!(shares.len(), TOP_PLAYS);
+        assert_eq!(shares[0].0, TOP_PLAYS + 4);
+        assert!(shares.iter().all(|&(i, _)| i != 99));
+        assert!(level(&[(0, 10.0, 0.0)]).is_none());
+    }
+
+    #[test]
+    fn shares_sum_to_one() {
+        let values: Vec<(usize, f64, f64)> = (0..TOP_PLAYS + 10)
+            .map(|i| (i, 10.0 + i as f64, 0.3 + (i % 7) as f64 / 10.0))
+            .collect();
+        let (_, shares) = level(&values).unwrap();
+        let total: f64 = shares.iter().map(|&(_, s)| s).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimate_counts_ignored_plays_and_reports_top_shares() {
+        let plays: Vec<ProfiledPlay> = (0..15)
+            .map(|i| profiled(20.0 + i as f64, 97.0))
+            .chain([profiled(40.0, 50.0)])
+            .collect();
+        let skill = estimate(&plays, 18, "overall", now());
+
+        assert_eq!(skill.plays_used, 15);
+        assert_eq!(skill.plays_ignored, 3);
+        assert_eq!(skill.top_plays.len(), TOP_PLAYS_REPORTED);
+        assert_eq!(skill.top_plays[0].rating, 34.0);
+        let reported: f64 = skill.top_plays.iter().map(|p| p.share).sum();
+        assert!(reported > 0.0 && reported < 1.0);
+        assert_eq!(skill.skillsets.len(), 1);
+        assert_eq!(skill.skillsets[0].pattern_type, "stream");
+    }
+}
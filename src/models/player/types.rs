@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Une partie servant à estimer le niveau d'un joueur
+#[derive(Debug, Clone, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct Play {
+    #[schema(example = 123456)]
+    pub beatmap_osu_id: i32,
+    #[schema(example = 100)]
+    pub centirate: i32,
+    /// Précision en pourcentage
+    #[schema(example = 96.42)]
+    pub accuracy: f64,
+    /// Sans date, la partie est considérée comme récente
+    pub played_at: Option<NaiveDateTime>,
+}
+
+/// Rate stocké correspondant à la partie d'index `idx` (1-based) de la requête
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PlayRateRow {
+    pub idx: i64,
+    pub rates_id: i32,
+    pub rating: Option<f64>,
+}
+
+/// Partie avec les valeurs de difficulté de son rate
+#[derive(Debug, Clone)]
+pub struct ProfiledPlay {
+    pub play: Play,
+    pub rating: Option<f64>,
+    pub skillsets: HashMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SkillRequestDto {
+    pub plays: Vec<Play>,
+    /// Type de rating du niveau global (overall par défaut)
+    #[schema(example = "overall")]
+    pub rating_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SkillsetLevelDto {
+    #[schema(example = "jumpstream")]
+    pub pattern_type: String,
+    pub value: f64,
+}
+
+/// Part d'une partie dans le niveau global
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PlayContributionDto {
+    pub beatmap_osu_id: i32,
+    pub centirate: i32,
+    pub accuracy: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub played_at: Option<NaiveDateTime>,
+    pub rating: f64,
+    /// Poids précision × rate × ancienneté, de 0 à 1
+    pub weight: f64,
+    /// Part du niveau global, de 0 à 1
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PlayerSkillDto {
+    pub rating_type: String,
+    /// Absent si aucune partie exploitable n'a de rating de ce type
+    pub overall: Option<f64>,
+    /// Niveau par skillset, pour ceux qui ont au moins une partie exploitable
+    pub skillsets: Vec<SkillsetLevelDto>,
+    /// Parties dont le rate est stocké et le poids non nul
+    pub plays_used: usize,
+    /// Parties ignorées : rate non stocké ou poids nul
+    pub plays_ignored: usize,
+    /// Parties qui pèsent le plus dans `overall`, de la plus importante à la moins importante
+    pub top_plays: Vec<PlayContributionDto>,
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use super::types::{CompactRateDto, RateDetailDto, RateRow, RatingEntry, SkillsetEntry};
+
+/// Id interne d'une difficulté à partir de son id osu!
+pub async fn find_beatmap_id(
+    pool: &PgPool,
+    beatmap_osu_id: i32,
+) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar::<_, i32>("SELECT id FROM beatmap WHERE osu_id = $1")
+        .bind(beatmap_osu_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Rates stockés d'une difficulté, par centirate croissant, bornes incluses
+pub async fn find_rates(
+    pool: &PgPool,
+    beatmap_id: i32,
+    min: Option<i32>,
+    max: Option<i32>,
+) -> Result<Vec<RateRow>, sqlx::Error> {
+    sqlx::query_as::<_, RateRow>(
+        r#"
+        SELECT id, centirate, drain_time, total_time, bpm
+        FROM rates
+        WHERE beatmap_id = $1
+          AND ($2::int4 IS NULL OR centirate >= $2)
+          AND ($3::int4 IS NULL OR centirate <= $3)
+        ORDER BY centirate
+        "#,
+    )
+    .bind(beatmap_id)
+    .bind(min)
+    .bind(max)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn find_ratings(
+    pool: &PgPool,
+    rates_ids: &[i32],
+) -> Result<Vec<RatingEntry>, sqlx::Error> {
+    sqlx::query_as::<_, RatingEntry>(
+        r#"
+        SELECT rates_id, rating_type, rating
+        FROM rating
+        WHERE rates_id = ANY($1)
+        ORDER BY rating_type
+        "#,
+    )
+    .bind(rates_ids)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn find_skillsets(
+    pool: &PgPool,
+    rates_ids: &[i32],
+) -> Result<Vec<SkillsetEntry>, sqlx::Error> {
+    sqlx::query_as::<_, SkillsetEntry>(
+        r#"
+        SELECT rates_id, pattern_type, pattern
+        FROM skillset
+        WHERE rates_id = ANY($1)
+        ORDER BY pattern_type
+        "#,
+    )
+    .bind(rates_ids)
+    .fetch_all(pool)
+    .await
+}
+
+/// Rates stockés avec leur seul rating `rating_type`
+pub async fn find_compact_rates(
+    pool: &PgPool,
+    beatmap_id: i32,
+    min: Option<i32>,
+    max: Option<i32>,
+    rating_type: &str,
+) -> Result<Vec<CompactRateDto>, sqlx::Error> {
+    let rows = find_rates(pool, beatmap_id, min, max).await?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<i32> = rows.iter().map(|r| r.id).collect();
+    let ratings = find_ratings(pool, &ids).await?;
+    Ok(compact_rates(&rows, &ratings, rating_type))
+}
+
+fn compact_rates(
+    rows: &[RateRow],
+    ratings: &[RatingEntry],
+    rating_type: &str,
+) -> Vec<CompactRateDto> {
+    let by_rate: HashMap<i32, f64> = ratings
+        .iter()
+        .filter(|r| r.rating_type == rating_type)
+        .map(|r| (r.rates_id, r.rating))
+        .collect();
+    rows.iter()
+        .map(|row| CompactRateDto {
+            centirate: row.centirate,
+            rating_type: rating_type.to_string(),
+            rating: by_rate.get(&row.id).copied(),
+        })
+        .collect()
+}
+
+/// Rates stockés avec leurs ratings et skillsets
+pub async fn find_rate_details(
+    pool: &PgPool,
+    beatmap_id: i32,
+    min: Option<i32>,
+    max: Option<i32>,
+) -> Result<Vec<RateDetailDto>, sqlx::Error> {
+    let rows = find_rates(pool, beatmap_id, min, max).await?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<i32> = rows.iter().map(|r| r.id).collect();
+    let mut index: HashMap<i32, usize> = HashMap::with_capacity(rows.len());
+    let mut rates: Vec<RateDetailDto> = Vec::with_capacity(rows.len());
+    for row in rows {
+        index.insert(row.id, rates.len());
+        rates.push(RateDetailDto::new(row));
+    }
+
+    for rating in find_ratings(pool, &ids).await? {
+        if let Some(&i) = index.get(&rating.rates_id) {
+            rates[i].ratings.push(rating);
+        }
+    }
+    for skillset in find_skillsets(pool, &ids).await? {
+        if let Some(&i) = index.get(&skillset.rates_id) {
+            rates[i].skillsets.push(skillset);
+        }
+    }
+    Ok(rates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: i32, centirate: i32) -> RateRow {
+        RateRow {
+            id,
+            centirate,
+            drain_time: 120,
+            total_time: 125_000,
+            bpm: 180.0,
+        }
+    }
+
+    fn rating(rates_id: i32, rating_type: &str, rating: f64) -> RatingEntry {
+        RatingEntry {
+            rates_id,
+            rating_type: rating_type.to_string(),
+            rating,
+        }
+    }
+
+    #[test]
+    fn compact_rates_keep_only_the_requested_rating() {
+        let rows = [row(1, 100), row(2, 110)];
+        let ratings = [
+            rating(1, "etterna", 20.5),
+            rating(1, "overall", 21.0),
+            rating(2, "etterna", 22.0),
+        ];
+
+        let compact = compact_rates(&rows, &ratings, "overall");
+        assert_eq!(
+            compact,
+            vec![
+                CompactRateDto {
+                    centirate: 100,
+                    rating_type: "overall".to_string(),
+                    rating: Some(21.0),
+                },
+                CompactRateDto {
+                    centirate: 110,
+                    rating_type: "overall".to_string(),
+                    rating: None,
+                },
+            ]
+        );
+    }
+}
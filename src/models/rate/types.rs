@@ -0,0 +1,93 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Ligne de `rates`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RateRow {
+    pub id: i32,
+    pub centirate: i32,
+    pub drain_time: i32,
+    pub total_time: i32,
+    pub bpm: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct RatingEntry {
+    #[serde(skip)]
+    pub rates_id: i32,
+    pub rating_type: String,
+    pub rating: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct SkillsetEntry {
+    #[serde(skip)]
+    pub rates_id: i32,
+    pub pattern_type: String,
+    pub pattern: f64,
+}
+
+/// Un rate complet : timings, ratings et skillsets. Sert de base aux
+/// estimations, et de réponse pour un rate estimé
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RateDetailDto {
+    #[schema(example = 100)]
+    pub centirate: i32,
+    /// Drain time en secondes
+    pub drain_time: i32,
+    /// Durée totale en millisecondes
+    pub total_time: i32,
+    pub bpm: f64,
+    pub ratings: Vec<RatingEntry>,
+    pub skillsets: Vec<SkillsetEntry>,
+    /// `false` pour un rate stocké, `true` pour une estimation
+    pub estimated: bool,
+    /// Détail de l'estimation ; `null` pour un rate stocké
+    pub estimate: Option<RateEstimate>,
+}
+
+impl RateDetailDto {
+    pub fn new(row: RateRow) -> Self {
+        Self {
+            centirate: row.centirate,
+            drain_time: row.drain_time,
+            total_time: row.total_time,
+            bpm: row.bpm,
+            ratings: Vec::new(),
+            skillsets: Vec::new(),
+            estimated: false,
+            estimate: None,
+        }
+    }
+}
+
+/// Un rate réduit à un seul rating, pour le mode compact de la liste
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CompactRateDto {
+    #[schema(example = 100)]
+    pub centirate: i32,
+    #[schema(example = "overall")]
+    pub rating_type: String,
+    /// `null` si le rate n'a pas ce rating
+    pub rating: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateMethod {
+    /// Entre deux rates stockés
+    Interpolated,
+    /// Prolongement des deux rates stockés les plus proches, d'un seul côté
+    Extrapolated,
+}
+
+/// Comment un rate non stocké a été estimé à partir des rates voisins
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RateEstimate {
+    pub method: EstimateMethod,
+    /// Entre 0 et 1 ; baisse avec la distance au rate stocké le plus proche
+    #[schema(example = 0.8)]
+    pub confidence: f64,
+    /// Rates stockés ayant servi à l'estimation
+    pub source_centirates: Vec<i32>,
+}
@@ -0,0 +1,73 @@
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use super::types::{RecommendationRow, SkillsetTarget};
+
+use crate::models::beatmapset::query::{push_difficulty_conditions, push_search_term};
+use crate::models::beatmapset::types::{CENTIRATE_RANGE, ListFilters, RateScope};
+
+/// Difficultés dont le skillset ciblé, dominant au rate retenu, tombe dans la
+/// fenêtre de `target` ; un rate par difficulté, le plus proche de la valeur
+/// idéale. Sans `rates[...]` dans les filtres, tous les rates sont candidats.
+pub async fn find_recommendations(
+    pool: &PgPool,
+    filters: &ListFilters,
+    target: &SkillsetTarget,
+    rating_type: &str,
+    played_by: Option<&str>,
+    exclude_beatmap_ids: &[i32],
+    limit: i64,
+) -> Result<Vec<RecommendationRow>, sqlx::Error> {
+    let mut filters = filters.clone();
+    if filters.rate.is_none() {
+        let (min, max) = CENTIRATE_RANGE;
+        filters.rate = Some(RateScope::Within { min, max });
+    }
+
+    let mut qb = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT * FROM (
+            SELECT DISTINCT ON (b.id)
+                   b.id, b.file_md5, b.osu_id, b.difficulty, b.status, b.od, b.bpm, b.total_time,
+                   bs.osu_id AS beatmapset_osu_id, bs.artist, bs.title, bs.creator,
+                   r.centirate, s.pattern AS map_value, rt.rating
+            FROM beatmapset bs
+            JOIN beatmap b ON b.beatmapset_id = bs.id
+            JOIN rates r ON r.beatmap_id = b.id
+            JOIN skillset s ON s.rates_id = r.id AND s.pattern_type = "#,
+    );
+    qb.push_bind(target.pattern_type.clone())
+        .push(" LEFT JOIN rating rt ON rt.rates_id = r.id AND rt.rating_type = ")
+        .push_bind(rating_type.to_string())
+        .push(" WHERE s.pattern BETWEEN ")
+        .push_bind(target.min)
+        .push(" AND ")
+        .push_bind(target.max)
+        .push(
+            " AND s.pattern >= ALL (SELECT s2.pattern FROM skillset s2 WHERE s2.rates_id = r.id)",
+        );
+    if !exclude_beatmap_ids.is_empty() {
+        qb.push(" AND b.id <> ALL(")
+            .push_bind(exclude_beatmap_ids.to_vec())
+            .push(")");
+    }
+    if let Some(player) = played_by {
+        qb.push(
+            " AND NOT EXISTS (SELECT 1 FROM score sc JOIN rates pr ON pr.id = sc.rates_id \
+             WHERE pr.beatmap_id = b.id AND sc.player = ",
+        )
+        .push_bind(player.to_string())
+        .push(")");
+    }
+    push_search_term(&mut qb, &filters);
+    push_difficulty_conditions(&mut qb, &filters);
+    qb.push(" ORDER BY b.id, ABS(s.pattern - ")
+        .push_bind(target.ideal)
+        .push(")) picked ORDER BY ABS(map_value - ")
+        .push_bind(target.ideal)
+        .push("), id LIMIT ")
+        .push_bind(limit);
+
+    qb.build_query_as::<RecommendationRow>()
+        .fetch_all(pool)
+        .await
+}
@@ -0,0 +1,93 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::models::beatmap::types::{BeatmapSummary, BeatmapWithSetRow, BeatmapsetSummary};
+use crate::models::player::types::SkillsetLevelDto;
+
+/// Fenêtre visée sur un skillset faible, juste au-dessus du niveau du joueur
+#[derive(Debug, Clone)]
+pub struct SkillsetTarget {
+    pub pattern_type: String,
+    pub level: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Valeur idéale, les maps les plus proches passent en premier
+    pub ideal: f64,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RecommendationRow {
+    #[sqlx(flatten)]
+    pub beatmap: BeatmapWithSetRow,
+    pub centirate: i32,
+    pub map_value: f64,
+    pub rating: Option<f64>,
+}
+
+/// Pourquoi une map est proposée
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecommendationReasonDto {
+    /// Skillset faible travaillé par la map, dominant à ce rate
+    #[schema(example = "jumpstream")]
+    pub pattern_type: String,
+    pub player_level: f64,
+    pub map_value: f64,
+    /// Écart relatif au niveau du joueur, en pourcentage
+    #[schema(example = 4.8)]
+    pub gap_percent: f64,
+    #[schema(
+        example = "jumpstream 23.05 at 1.10x, 4.8% above your level (22.00); main skillset of this rate"
+    )]
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecommendationDto {
+    pub beatmap: BeatmapSummary,
+    pub beatmapset: BeatmapsetSummary,
+    /// Rate auquel jouer la map
+    pub centirate: i32,
+    pub rating: Option<f64>,
+    pub reason: RecommendationReasonDto,
+}
+
+impl RecommendationDto {
+    pub fn new(row: RecommendationRow, target: &SkillsetTarget) -> Self {
+        let gap_percent = if target.level > 0.0 {
+            (row.map_value - target.level) / target.level * 100.0
+        } else {
+            0.0
+        };
+        let summary = format!(
+            "{} {:.2} at {:.2}x, {:.1}% above your level ({:.2}); main skillset of this rate",
+            target.pattern_type,
+            row.map_value,
+            f64::from(row.centirate) / 100.0,
+            gap_percent,
+            target.level
+        );
+        let (beatmap, beatmapset) = row.beatmap.into_parts();
+        Self {
+            beatmap,
+            beatmapset,
+            centirate: row.centirate,
+            rating: row.rating,
+            reason: RecommendationReasonDto {
+                pattern_type: target.pattern_type.clone(),
+                player_level: target.level,
+                map_value: row.map_value,
+                gap_percent,
+                summary,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecommendationsDto {
+    /// Niveau par skillset utilisé (calculé et/ou fourni)
+    pub profile: Vec<SkillsetLevelDto>,
+    /// Skillsets les plus faibles du profil, ciblés par les recommandations
+    pub weak_skillsets: Vec<String>,
+    pub recommendations: Vec<RecommendationDto>,
+}
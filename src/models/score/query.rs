@@ -0,0 +1,123 @@
+use sqlx::PgPool;
+
+use super::types::{NewScore, ScoreDto, ScoreRateRow};
+
+/// Colonnes de `ScoreDto` ; attend les alias `sc` (score), `r` (rates) et `b` (beatmap)
+const SCORE_COLUMNS: &str = r#"
+    sc.id, sc.player, b.osu_id AS beatmap_osu_id, r.centirate, sc.accuracy,
+    sc.count_perfect, sc.count_great, sc.count_good, sc.count_ok, sc.count_meh, sc.count_miss,
+    sc.max_combo, sc.played_at
+"#;
+
+/// Rate stocké d'une difficulté, avec son nombre de notes
+pub async fn find_score_rate(
+    pool: &PgPool,
+    beatmap_id: i32,
+    centirate: i32,
+) -> Result<Option<ScoreRateRow>, sqlx::Error> {
+    sqlx::query_as::<_, ScoreRateRow>(
+        "SELECT id, note_count FROM rates WHERE beatmap_id = $1 AND centirate = $2",
+    )
+    .bind(beatmap_id)
+    .bind(centirate)
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn insert_score(pool: &PgPool, score: &NewScore) -> Result<ScoreDto, sqlx::Error> {
+    let sql = format!(
+        r#"
+        WITH sc AS (
+            INSERT INTO score (
+                rates_id, player, accuracy,
+                count_perfect, count_great, count_good, count_ok, count_meh, count_miss,
+                max_combo, played_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+        )
+        SELECT {SCORE_COLUMNS}
+        FROM sc
+        JOIN rates r ON r.id = sc.rates_id
+        JOIN beatmap b ON b.id = r.beatmap_id
+        "#
+    );
+    let j = score.judgements;
+    sqlx::query_as::<_, ScoreDto>(&sql)
+        .bind(score.rates_id)
+        .bind(&score.player)
+        .bind(score.accuracy)
+        .bind(j.perfect)
+        .bind(j.great)
+        .bind(j.good)
+        .bind(j.ok)
+        .bind(j.meh)
+        .bind(j.miss)
+        .bind(score.max_combo)
+        .bind(score.played_at)
+        .fetch_one(pool)
+        .await
+}
+
+/// Scores d'une difficulté, éventuellement limités à un rate, par précision décroissante
+pub async fn find_beatmap_scores(
+    pool: &PgPool,
+    beatmap_id: i32,
+    centirate: Option<i32>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ScoreDto>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        SELECT {SCORE_COLUMNS}
+        FROM score sc
+        JOIN rates r ON r.id = sc.rates_id
+        JOIN beatmap b ON b.id = r.beatmap_id
+        WHERE b.id = $1
+          AND ($2::int4 IS NULL OR r.centirate = $2)
+        ORDER BY sc.accuracy DESC, sc.played_at, sc.id
+        LIMIT $3 OFFSET $4
+        "#
+    );
+    sqlx::query_as::<_, ScoreDto>(&sql)
+        .bind(beatmap_id)
+        .bind(centirate)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+}
+
+/// Meilleur score du joueur sur chaque difficulté : le rate le mieux noté,
+/// départagé par la précision. Trié par rating décroissant.
+pub async fn find_best_scores(
+    pool: &PgPool,
+    player: &str,
+    rating_type: &str,
+    min_accuracy: Option<f64>,
+    limit: i64,
+) -> Result<Vec<ScoreDto>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        SELECT * FROM (
+            SELECT DISTINCT ON (b.id) {SCORE_COLUMNS}, rt.rating
+            FROM score sc
+            JOIN rates r ON r.id = sc.rates_id
+            JOIN beatmap b ON b.id = r.beatmap_id
+            LEFT JOIN rating rt ON rt.rates_id = r.id AND rt.rating_type = $2
+            WHERE sc.player = $1
+              AND ($3::float8 IS NULL OR sc.accuracy >= $3)
+            ORDER BY b.id, rt.rating DESC NULLS LAST, sc.accuracy DESC, sc.played_at
+        ) best
+        ORDER BY rating DESC NULLS LAST, accuracy DESC, id
+        LIMIT $4
+        "#
+    );
+    sqlx::query_as::<_, ScoreDto>(&sql)
+        .bind(player)
+        .bind(rating_type)
+        .bind(min_accuracy)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
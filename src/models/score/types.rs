@@ -0,0 +1,118 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longueur maximale d'un nom de joueur
+pub const MAX_PLAYER_LEN: usize = 32;
+
+/// Écart toléré, en points de pourcentage, entre la précision envoyée et celle
+/// recalculée depuis les jugements (arrondis côté client)
+pub const ACCURACY_TOLERANCE: f64 = 0.01;
+
+/// Jugements osu!mania, du meilleur au pire
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct Judgements {
+    /// MAX / 320
+    #[sqlx(rename = "count_perfect")]
+    pub perfect: i32,
+    /// 300
+    #[sqlx(rename = "count_great")]
+    pub great: i32,
+    /// 200
+    #[sqlx(rename = "count_good")]
+    pub good: i32,
+    /// 100
+    #[sqlx(rename = "count_ok")]
+    pub ok: i32,
+    /// 50
+    #[sqlx(rename = "count_meh")]
+    pub meh: i32,
+    #[sqlx(rename = "count_miss")]
+    pub miss: i32,
+}
+
+impl Judgements {
+    pub fn total(&self) -> i64 {
+        [
+            self.perfect,
+            self.great,
+            self.good,
+            self.ok,
+            self.meh,
+            self.miss,
+        ]
+        .iter()
+        .map(|&c| c as i64)
+        .sum()
+    }
+
+    /// Précision ScoreV1 en pourcentage (MAX et 300 valent autant)
+    pub fn accuracy(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let points = 300 * (self.perfect as i64 + self.great as i64)
+            + 200 * self.good as i64
+            + 100 * self.ok as i64
+            + 50 * self.meh as i64;
+        Some(points as f64 * 100.0 / (300 * total) as f64)
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ScoreSubmitDto {
+    #[schema(example = 123456)]
+    pub beatmap_osu_id: i32,
+    /// Rate joué ; doit être stocké pour la difficulté
+    #[schema(example = 110)]
+    pub centirate: i32,
+    #[schema(example = "cookiezi")]
+    pub player: String,
+    /// Précision en pourcentage ; toujours recalculée depuis les jugements (ScoreV1),
+    /// la valeur envoyée doit y correspondre à 0,01 près
+    #[schema(example = 96.42)]
+    pub accuracy: Option<f64>,
+    /// Un jugement par note ; le total doit égaler le nombre de notes du rate
+    pub judgements: Judgements,
+    /// Au plus le nombre de jugements
+    pub max_combo: i32,
+    /// Date de la partie ; maintenant par défaut
+    pub played_at: Option<NaiveDateTime>,
+}
+
+/// Rate stocké visé par un score
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ScoreRateRow {
+    pub id: i32,
+    /// Notes et longues notes ; absent pour les rates importés avant son calcul
+    pub note_count: Option<i32>,
+}
+
+/// Score validé, prêt à être enregistré
+#[derive(Debug, Clone)]
+pub struct NewScore {
+    pub rates_id: i32,
+    pub player: String,
+    pub accuracy: f64,
+    pub judgements: Judgements,
+    pub max_combo: i32,
+    pub played_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct ScoreDto {
+    pub id: i32,
+    pub player: String,
+    pub beatmap_osu_id: Option<i32>,
+    pub centirate: i32,
+    pub accuracy: f64,
+    #[sqlx(flatten)]
+    pub judgements: Judgements,
+    pub max_combo: i32,
+    pub played_at: NaiveDateTime,
+    /// Rating du rate joué, pour les meilleurs scores d'un joueur
+    #[sqlx(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<f64>,
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use super::types::{
+    MapScoreDto, StandingDto, WeeklyBeatmapDto, WeeklyBeatmapRow, WeeklyDto, WeeklyRow,
+};
+use crate::models::beatmapset::query::{push_difficulty_conditions, push_search_term};
+use crate::models::beatmapset::types::ListFilters;
+
+const WEEKLY_COLUMNS: &str = "w.id, w.starts_at, w.ends_at, w.seed, w.filters";
+
+/// Tire `count` difficultés parmi celles qui correspondent aux filtres, au plus
+/// une par beatmapset. Le tirage ne dépend que de la graine et du pool : les
+/// candidats sont ordonnés par `md5(graine:id)`, un rate est choisi de la même
+/// façon parmi ceux de la fenêtre.
+pub async fn select_weekly_beatmaps(
+    pool: &PgPool,
+    filters: &ListFilters,
+    seed: i64,
+    count: i64,
+) -> Result<Vec<WeeklyBeatmapRow>, sqlx::Error> {
+    let seed = seed.to_string();
+    let mut qb = QueryBuilder::<Postgres>::new(
+        r#"
+        WITH per_beatmap AS (
+            SELECT DISTINCT ON (b.id) b.id AS beatmap_id, b.beatmapset_id, r.centirate
+            FROM beatmapset bs
+            JOIN beatmap b ON b.beatmapset_id = bs.id
+            JOIN rates r ON r.beatmap_id = b.id
+            WHERE TRUE"#,
+    );
+    push_search_term(&mut qb, filters);
+    push_difficulty_conditions(&mut qb, filters);
+    qb.push(" ORDER BY b.id, md5(")
+        .push_bind(seed.clone())
+        .push(
+            r#" || ':' || r.id)
+        ),
+        per_set AS (
+            SELECT DISTINCT ON (beatmapset_id) beatmap_id, centirate
+            FROM per_beatmap
+            ORDER BY beatmapset_id, md5("#,
+        )
+        .push_bind(seed.clone())
+        .push(
+            r#" || ':' || beatmap_id)
+        ),
+        picked AS (
+            SELECT beatmap_id, centirate,
+                   ROW_NUMBER() OVER (ORDER BY md5("#,
+        )
+        .push_bind(seed.clone())
+        .push(
+            r#" || ':' || beatmap_id))::int4 AS position
+            FROM per_set
+        )
+        SELECT NULL::int4 AS weekly_id, p.position, p.centirate,
+               b.id, b.file_md5, b.osu_id, b.difficulty, b.status, b.od, b.bpm, b.total_time,
+               bs.osu_id AS beatmapset_osu_id, bs.artist, bs.title, bs.creator
+        FROM picked p
+        JOIN beatmap b ON b.id = p.beatmap_id
+        JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE p.position <= "#,
+        )
+        .push_bind(count)
+        .push(" ORDER BY p.position");
+
+    qb.build_query_as::<WeeklyBeatmapRow>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Enregistre une semaine et sa sélection ; `None` si la semaine existe déjà
+pub async fn insert_weekly(
+    pool: &PgPool,
+    starts_at: NaiveDateTime,
+    ends_at: NaiveDateTime,
+    seed: i64,
+    filters: &str,
+    beatmaps: &[WeeklyBeatmapRow],
+) -> Result<Option<WeeklyRow>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let inserted = sqlx::query_as::<_, WeeklyRow>(
+        r#"
+        INSERT INTO weekly (starts_at, ends_at, seed, filters)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (starts_at) DO NOTHING
+        RETURNING id, starts_at, ends_at, seed, filters
+        "#,
+    )
+    .bind(starts_at)
+    .bind(ends_at)
+    .bind(seed)
+    .bind(filters)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(weekly) = inserted else {
+        return Ok(None);
+    };
+
+    let positions: Vec<i32> = beatmaps.iter().map(|b| b.position).collect();
+    let beatmap_ids: Vec<i32> = beatmaps.iter().map(|b| b.beatmap.id).collect();
+    let centirates: Vec<i32> = beatmaps.iter().map(|b| b.centirate).collect();
+    sqlx::query(
+        r#"
+        INSERT INTO weekly_beatmap (weekly_id, position, beatmap_id, centirate)
+        SELECT $1, * FROM UNNEST($2::int4[], $3::int4[], $4::int4[])
+        "#,
+    )
+    .bind(weekly.id)
+    .bind(positions)
+    .bind(beatmap_ids)
+    .bind(centirates)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(weekly))
+}
+
+pub async fn find_weekly(pool: &PgPool, id: i32) -> Result<Option<WeeklyRow>, sqlx::Error> {
+    let sql = format!("SELECT {WEEKLY_COLUMNS} FROM weekly w WHERE w.id = $1");
+    sqlx::query_as::<_, WeeklyRow>(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Semaine en cours à `now`
+pub async fn find_current_weekly(
+    pool: &PgPool,
+    now: NaiveDateTime,
+) -> Result<Option<WeeklyRow>, sqlx::Error> {
+    let sql =
+        format!("SELECT {WEEKLY_COLUMNS} FROM weekly w WHERE w.starts_at <= $1 AND w.ends_at > $1");
+    sqlx::query_as::<_, WeeklyRow>(&sql)
+        .bind(now)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Semaines terminées à `now`, de la plus récente à la plus ancienne
+pub async fn find_past_weeklies(
+    pool: &PgPool,
+    now: NaiveDateTime,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<WeeklyRow>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        SELECT {WEEKLY_COLUMNS}
+        FROM weekly w
+        WHERE w.ends_at <= $1
+        ORDER BY w.starts_at DESC
+        LIMIT $2 OFFSET $3
+        "#
+    );
+    sqlx::query_as::<_, WeeklyRow>(&sql)
+        .bind(now)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+}
+
+/// Sélections des semaines données, par semaine puis position
+pub async fn find_weekly_beatmaps(
+    pool: &PgPool,
+    weekly_ids: &[i32],
+) -> Result<Vec<WeeklyBeatmapRow>, sqlx::Error> {
+    sqlx::query_as::<_, WeeklyBeatmapRow>(
+        r#"
+        SELECT wb.weekly_id, wb.position, wb.centirate,
+               b.id, b.file_md5, b.osu_id, b.difficulty, b.status, b.od, b.bpm, b.total_time,
+               bs.osu_id AS beatmapset_osu_id, bs.artist, bs.title, bs.creator
+        FROM weekly_beatmap wb
+        JOIN beatmap b ON b.id = wb.beatmap_id
+        JOIN beatmapset bs ON bs.id = b.beatmapset_id
+        WHERE wb.weekly_id = ANY($1)
+        ORDER BY wb.weekly_id, wb.position
+        "#,
+    )
+    .bind(weekly_ids)
+    .fetch_all(pool)
+    .await
+}
+
+/// Meilleur score de chaque joueur sur chaque map de la semaine `$1` : scores
+/// enregistrés au rate de la sélection, joués pendant la semaine
+const WEEK_BEST_SCORES: &str = r#"
+    week_best AS (
+        SELECT DISTINCT ON (wb.beatmap_id, sc.player)
+               wb.beatmap_id, sc.player, sc.accuracy, sc.played_at
+        FROM weekly w
+        JOIN weekly_beatmap wb ON wb.weekly_id = w.id
+        JOIN rates r ON r.beatmap_id = wb.beatmap_id AND r.centirate = wb.centirate
+        JOIN score sc ON sc.rates_id = r.id
+        WHERE w.id = $1 AND sc.played_at >= w.starts_at AND sc.played_at < w.ends_at
+        ORDER BY wb.beatmap_id, sc.player, sc.accuracy DESC, sc.played_at
+    )
+"#;
+
+/// Classement général de la semaine, ex-aequo au même rang
+pub async fn find_standings(
+    pool: &PgPool,
+    weekly_id: i32,
+    limit: i64,
+) -> Result<Vec<StandingDto>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        WITH {WEEK_BEST_SCORES}
+        SELECT RANK() OVER (ORDER BY SUM(accuracy) DESC) AS rank,
+               player,
+               SUM(accuracy) AS total_accuracy,
+               COUNT(*) AS maps_played,
+               MAX(played_at) AS last_played_at
+        FROM week_best
+        GROUP BY player
+        ORDER BY rank, last_played_at, player
+        LIMIT $2
+        "#
+    );
+    sqlx::query_as::<_, StandingDto>(&sql)
+        .bind(weekly_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/// Les `limit` meilleurs scores de chaque map de la semaine
+pub async fn find_map_scores(
+    pool: &PgPool,
+    weekly_id: i32,
+    limit: i64,
+) -> Result<Vec<MapScoreDto>, sqlx::Error> {
+    let sql = format!(
+        r#"
+        WITH {WEEK_BEST_SCORES}
+        SELECT beatmap_id, rank, player, accuracy, played_at
+        FROM (
+            SELECT beatmap_id, player, accuracy, played_at,
+                   RANK() OVER (PARTITION BY beatmap_id ORDER BY accuracy DESC) AS rank,
+                   ROW_NUMBER() OVER (
+                       PARTITION BY beatmap_id ORDER BY accuracy DESC, played_at
+                   ) AS row_number
+            FROM week_best
+        ) ranked
+        WHERE row_number <= $2
+        ORDER BY beatmap_id, row_number
+        "#
+    );
+    sqlx::query_as::<_, MapScoreDto>(&sql)
+        .bind(weekly_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/// Semaines complètes, avec leur sélection
+pub async fn attach_weekly_beatmaps(
+    pool: &PgPool,
+    weeklies: Vec<WeeklyRow>,
+) -> Result<Vec<WeeklyDto>, sqlx::Error> {
+    let ids: Vec<i32> = weeklies.iter().map(|w| w.id).collect();
+    let mut by_weekly: HashMap<i32, Vec<WeeklyBeatmapDto>> = HashMap::new();
+    for row in find_weekly_beatmaps(pool, &ids).await? {
+        by_weekly
+            .entry(row.weekly_id.unwrap_or_default())
+            .or_default()
+            .push(row.into());
+    }
+    Ok(weeklies
+        .into_iter()
+        .map(|w| {
+            let beatmaps = by_weekly.remove(&w.id).unwrap_or_default();
+            WeeklyDto::new(w, beatmaps)
+        })
+        .collect())
+}
@@ -0,0 +1,179 @@
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::beatmap::types::{BeatmapSummary, BeatmapWithSetRow, BeatmapsetSummary};
+
+/// Nombre de maps tirées sans `count`
+pub const DEFAULT_WEEKLY_COUNT: i64 = 5;
+pub const MAX_WEEKLY_COUNT: i64 = 20;
+
+/// Semaine contenant `date` : du lundi 00:00 au lundi suivant
+pub fn week_bounds(date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let monday = date - Days::new(date.weekday().num_days_from_monday() as u64);
+    let starts_at = monday.and_hms_opt(0, 0, 0).unwrap_or_default();
+    (starts_at, starts_at + Days::new(7))
+}
+
+/// Graine par défaut d'une semaine : année et numéro de semaine ISO (`202642`)
+pub fn default_seed(date: NaiveDate) -> i64 {
+    let week = date.iso_week();
+    week.year() as i64 * 100 + week.week() as i64
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WeeklyRow {
+    pub id: i32,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub seed: i64,
+    pub filters: String,
+}
+
+/// Map d'une semaine, avec sa position dans la sélection
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WeeklyBeatmapRow {
+    /// Absent pour une sélection pas encore enregistrée
+    pub weekly_id: Option<i32>,
+    pub position: i32,
+    pub centirate: i32,
+    #[sqlx(flatten)]
+    pub beatmap: BeatmapWithSetRow,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WeeklyBeatmapDto {
+    pub position: i32,
+    /// Rate auquel la map est jouée cette semaine
+    pub centirate: i32,
+    pub beatmap: BeatmapSummary,
+    pub beatmapset: BeatmapsetSummary,
+}
+
+impl From<WeeklyBeatmapRow> for WeeklyBeatmapDto {
+    fn from(row: WeeklyBeatmapRow) -> Self {
+        let (beatmap, beatmapset) = row.beatmap.into_parts();
+        Self {
+            position: row.position,
+            centirate: row.centirate,
+            beatmap,
+            beatmapset,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WeeklyDto {
+    /// Absent pour un aperçu (`dry_run`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    /// Graine du tirage : mêmes filtres et même graine donnent la même sélection
+    pub seed: i64,
+    /// Query string des filtres du tirage
+    #[schema(example = "rating[rating_min]=20&beatmap_technical[status]=ranked")]
+    pub filters: String,
+    pub beatmaps: Vec<WeeklyBeatmapDto>,
+}
+
+impl WeeklyDto {
+    pub fn new(row: WeeklyRow, beatmaps: Vec<WeeklyBeatmapDto>) -> Self {
+        Self {
+            id: Some(row.id),
+            starts_at: row.starts_at,
+            ends_at: row.ends_at,
+            seed: row.seed,
+            filters: row.filters,
+            beatmaps,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WeeklyCreateDto {
+    /// Un jour de la semaine visée ; la semaine en cours par défaut
+    #[schema(example = "2026-10-19")]
+    pub starts_on: Option<NaiveDate>,
+    /// Graine du tirage ; par défaut l'année et la semaine ISO
+    #[schema(example = 202643)]
+    pub seed: Option<i64>,
+    /// Nombre de maps (5 par défaut, 20 au plus)
+    #[schema(example = 5)]
+    pub count: Option<i64>,
+    /// Calcule la sélection sans l'enregistrer
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Classement général : somme des meilleures précisions sur les maps de la semaine,
+/// d'après les scores joués au rate de la sélection pendant la semaine
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct StandingDto {
+    pub rank: i64,
+    pub player: String,
+    pub total_accuracy: f64,
+    pub maps_played: i64,
+    pub last_played_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct MapScoreDto {
+    #[serde(skip)]
+    pub beatmap_id: i32,
+    pub rank: i64,
+    pub player: String,
+    pub accuracy: f64,
+    pub played_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MapLeaderboardDto {
+    pub position: i32,
+    pub centirate: i32,
+    pub beatmap_osu_id: Option<i32>,
+    pub scores: Vec<MapScoreDto>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WeeklyLeaderboardDto {
+    pub weekly_id: i32,
+    pub standings: Vec<StandingDto>,
+    /// Classement de chaque map, dans l'ordre de la sélection
+    pub beatmaps: Vec<MapLeaderboardDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn week_runs_from_monday_to_monday() {
+        let (starts_at, ends_at) = week_bounds(date(2026, 10, 18));
+        assert_eq!(starts_at, date(2026, 10, 12).and_hms_opt(0, 0, 0).unwrap());
+        assert_eq!(ends_at, date(2026, 10, 19).and_hms_opt(0, 0, 0).unwrap());
+
+        assert_eq!(week_bounds(date(2026, 10, 12)), (starts_at, ends_at));
+        assert_eq!(week_bounds(date(2026, 10, 19)).0, ends_at);
+    }
+
+    #[test]
+    fn week_can_span_two_years() {
+        let (starts_at, ends_at) = week_bounds(date(2027, 1, 1));
+        assert_eq!(starts_at, date(2026, 12, 28).and_hms_opt(0, 0, 0).unwrap());
+        assert_eq!(ends_at, date(2027, 1, 4).and_hms_opt(0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn default_seed_is_the_iso_week() {
+        assert_eq!(default_seed(date(2026, 10, 18)), 202642);
+        assert_eq!(default_seed(date(2026, 10, 12)), 202642);
+        assert_eq!(default_seed(date(2026, 10, 19)), 202643);
+        // Semaine ISO 53 de 2026, même pour un jour de 2027
+        assert_eq!(default_seed(date(2027, 1, 1)), 202653);
+    }
+}
@@ -0,0 +1,27 @@
+//! # API Key Routes Module
+//!
+//! Ce module configure les routes de gestion des clés d'API (scope `admin`).
+
+use crate::handlers;
+use crate::middleware::auth::{Scope, require_scope};
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{delete, get},
+};
+use db::db::DatabaseManager;
+
+pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/keys",
+            get(handlers::api_keys::get::list::handler)
+                .post(handlers::api_keys::post::create::handler),
+        )
+        .route(
+            "/keys/{id}",
+            delete(handlers::api_keys::delete::revoke::handler),
+        )
+        .layer(from_fn_with_state(Scope::Admin, require_scope))
+        .with_state(db)
+}
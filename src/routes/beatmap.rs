@@ -3,8 +3,12 @@
 //! Ce module configure les routes de beatmap.
 
 use crate::handlers;
+use crate::middleware::auth::{Scope, require_scope};
+use crate::middleware::cache::cache_response;
+use crate::middleware::etag::{cache_control, conditional_get};
 use axum::{
     Router,
+    middleware::{from_fn, from_fn_with_state},
     routing::{get, post},
 };
 use db::db::DatabaseManager;
@@ -13,15 +17,49 @@ pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
     Router::new()
         .route(
             "/beatmaps/imports",
-            post(handlers::beatmapsets::batch::checksums::handler),
+            post(handlers::beatmapsets::batch::checksums::handler)
+                .layer(from_fn_with_state(Scope::Import, require_scope)),
+        )
+        .route(
+            "/beatmaps/by-hash",
+            post(handlers::beatmap::post::by_hashes::handler),
+        )
+        .route(
+            "/beatmaps/by-hash/{md5}",
+            get(handlers::beatmap::get::by_hash::handler),
+        )
+        .route(
+            "/beatmaps/{beatmap_osu_id}",
+            get(handlers::beatmap::get::find_one::handler)
+                .layer(from_fn(cache_response))
+                .layer(from_fn_with_state(
+                    cache_control::BEATMAPSET,
+                    conditional_get,
+                )),
+        )
+        .route(
+            "/beatmaps/{beatmap_osu_id}/similar",
+            get(handlers::beatmap::get::similar::handler)
+                .layer(from_fn(cache_response))
+                .layer(from_fn_with_state(cache_control::SIMILAR, conditional_get)),
+        )
+        .route(
+            "/beatmaps/{beatmap_osu_id}/rates",
+            get(handlers::beatmapsets::rate::list::handler)
+                .layer(from_fn(cache_response))
+                .layer(from_fn_with_state(cache_control::RATE, conditional_get)),
         )
         .route(
             "/beatmaps/{beatmap_osu_id}/rates/{centirate}",
-            get(handlers::beatmapsets::rate::handler),
+            get(handlers::beatmapsets::rate::handler)
+                .layer(from_fn(cache_response))
+                .layer(from_fn_with_state(cache_control::RATE, conditional_get)),
         )
         .route(
             "/beatmapsets",
-            get(handlers::beatmapsets::get::list::handler),
+            get(handlers::beatmapsets::get::list::handler)
+                .layer(from_fn(cache_response))
+                .layer(from_fn_with_state(cache_control::LIST, conditional_get)),
         )
         .route(
             "/beatmapsets/random",
@@ -29,7 +67,12 @@ pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
         )
         .route(
             "/beatmapsets/{osu_id}",
-            get(handlers::beatmapsets::get::by_osu_id::handler),
+            get(handlers::beatmapsets::get::by_osu_id::handler)
+                .layer(from_fn(cache_response))
+                .layer(from_fn_with_state(
+                    cache_control::BEATMAPSET,
+                    conditional_get,
+                )),
         )
         .with_state(db)
 }
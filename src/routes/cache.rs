@@ -0,0 +1,23 @@
+//! # Cache Routes Module
+//!
+//! Ce module configure les routes de suivi et d'invalidation du cache.
+
+use crate::handlers;
+use crate::middleware::auth::{Scope, require_scope};
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
+use db::db::DatabaseManager;
+
+pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
+    Router::new()
+        .route("/cache/stats", get(handlers::cache::stats::handler))
+        .route(
+            "/cache/invalidate",
+            post(handlers::cache::invalidate::handler)
+                .layer(from_fn_with_state(Scope::Import, require_scope)),
+        )
+        .with_state(db)
+}
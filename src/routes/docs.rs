@@ -10,13 +10,43 @@ use utoipa_redoc::{Redoc, Servable as RedocServable};
 use utoipa_scalar::{Scalar, Servable as ScalarServable};
 use utoipa_swagger_ui::SwaggerUi;
 #[derive(OpenApi)]
-#[openapi(paths(
-    crate::handlers::beatmapsets::batch::checksums::handler,
-    crate::handlers::pending_beatmap::get::status_by_osu_id::handler,
-    crate::handlers::beatmapsets::get::list::handler,
-    crate::handlers::beatmapsets::get::by_osu_id::handler,
-    crate::handlers::beatmapsets::rate::handler
-))]
+#[openapi(
+    paths(
+        crate::handlers::beatmapsets::batch::checksums::handler,
+        crate::handlers::beatmap::get::by_hash::handler,
+        crate::handlers::beatmap::post::by_hashes::handler,
+        crate::handlers::beatmap::get::find_one::handler,
+        crate::handlers::beatmap::get::similar::handler,
+        crate::handlers::pending_beatmap::get::events::handler,
+        crate::handlers::pending_beatmap::get::status_by_osu_id::handler,
+        crate::handlers::pending_beatmap::post::status_by_hashes::handler,
+        crate::handlers::beatmapsets::get::list::handler,
+        crate::handlers::beatmapsets::get::by_osu_id::handler,
+        crate::handlers::beatmapsets::rate::handler,
+        crate::handlers::beatmapsets::rate::list::handler,
+        crate::handlers::cache::stats::handler,
+        crate::handlers::cache::invalidate::handler,
+        crate::handlers::weekly::get::current::handler,
+        crate::handlers::weekly::get::list::handler,
+        crate::handlers::weekly::get::find_one::handler,
+        crate::handlers::weekly::get::leaderboard::handler,
+        crate::handlers::weekly::post::create::handler,
+        crate::handlers::scores::post::submit::handler,
+        crate::handlers::scores::get::by_beatmap::handler,
+        crate::handlers::scores::get::best_by_player::handler,
+        crate::handlers::players::get::skill::handler,
+        crate::handlers::players::post::skill::handler,
+        crate::handlers::recommendations::get::list::handler,
+        crate::handlers::api_keys::get::list::handler,
+        crate::handlers::api_keys::post::create::handler,
+        crate::handlers::api_keys::delete::revoke::handler
+    ),
+    components(schemas(
+        crate::models::beatmapset::types::BeatmapsetCursorPage,
+        crate::models::rate::types::CompactRateDto,
+        crate::models::rate::types::RateDetailDto
+    ))
+)]
 struct ApiDoc;
 
 pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
@@ -12,29 +12,58 @@
 //! 3. Ajoutez le module dans ce fichier
 //! 4. Utilisez `merge()` pour combiner les routes
 
-use axum::Router;
+use axum::{
+    Router,
+    middleware::{from_fn, from_fn_with_state},
+};
 use db::db::DatabaseManager;
 
+use crate::config::AuthConfig;
+use crate::middleware::auth::{AuthState, authenticate};
+use crate::middleware::rate_limit::{pre_limit, rate_limit};
+
 // Re-export all route modules here
+pub mod api_keys;
 pub mod beatmap;
+pub mod cache;
 pub mod docs;
 pub mod help;
 pub mod pending_beatmap;
-//pub mod scores;
-//pub mod weekly;
+pub mod players;
+pub mod recommendations;
+pub mod scores;
+pub mod weekly;
 
-pub fn create_router(db: DatabaseManager) -> Router {
-    Router::new()
-        // Routes API
+pub fn create_router(db: DatabaseManager, auth: &AuthConfig) -> Router {
+    let auth_state = AuthState {
+        db: db.clone(),
+        require_read_key: auth.require_read_key,
+    };
+
+    // Routes API, derrière l'authentification par clé ; la limite de débit
+    // passe après pour compter par clé quand il y en a une, et `pre_limit`
+    // avant pour borner par IP les vérifications de clés
+    let api = Router::new()
+        .nest("/api", api_keys::router(db.clone()))
         .nest("/api", beatmap::router(db.clone()))
+        .nest("/api", cache::router(db.clone()))
         .nest("/api", help::router())
-        .merge(docs::router(db.clone()))
         .nest("/api", pending_beatmap::router(db.clone()))
-        //.nest("/api", scores::router(db.clone()))
-        //.nest("/api", weekly::router(db.clone()))
+        .nest("/api", players::router(db.clone()))
+        .nest("/api", recommendations::router(db.clone()))
+        .nest("/api", scores::router(db.clone()))
+        .nest("/api", weekly::router(db.clone()))
         // Add your other route modules here
         // Example:
         // .nest("/api", user::router())
         // .nest("/api", product::router())
+        .layer(from_fn(rate_limit))
+        .layer(from_fn_with_state(auth_state, authenticate))
+        .layer(from_fn(pre_limit));
+
+    Router::new()
+        .merge(api)
+        .merge(docs::router(db.clone()))
+        .fallback(crate::error::route_not_found)
         .with_state(db)
 }
@@ -3,11 +3,22 @@
 //! Ce module configure les routes de beatmap.
 
 use crate::handlers;
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 use db::db::DatabaseManager;
 
 pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
     Router::new()
+        .route(
+            "/pending_beatmap/events",
+            get(handlers::pending_beatmap::get::events::handler),
+        )
+        .route(
+            "/pending_beatmap/status",
+            post(handlers::pending_beatmap::post::status_by_hashes::handler),
+        )
         .route(
             "/pending_beatmap/status/{id}",
             get(handlers::pending_beatmap::get::status_by_osu_id::handler),
@@ -0,0 +1,27 @@
+//! # Players Routes Module
+//!
+//! Ce module configure les routes propres aux joueurs.
+
+use crate::handlers;
+use axum::{
+    Router,
+    routing::{get, post},
+};
+use db::db::DatabaseManager;
+
+pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/players/skill",
+            post(handlers::players::post::skill::handler),
+        )
+        .route(
+            "/players/{player}/scores/best",
+            get(handlers::scores::get::best_by_player::handler),
+        )
+        .route(
+            "/players/{player}/skill",
+            get(handlers::players::get::skill::handler),
+        )
+        .with_state(db)
+}
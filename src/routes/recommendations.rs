@@ -0,0 +1,16 @@
+//! # Recommendations Routes Module
+//!
+//! Ce module configure les routes de recommandation de maps.
+
+use crate::handlers;
+use axum::{Router, routing::get};
+use db::db::DatabaseManager;
+
+pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/recommendations",
+            get(handlers::recommendations::get::list::handler),
+        )
+        .with_state(db)
+}
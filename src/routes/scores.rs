@@ -0,0 +1,26 @@
+//! # Scores Routes Module
+//!
+//! Ce module configure les routes des scores.
+
+use crate::handlers;
+use crate::middleware::auth::{Scope, require_scope};
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
+use db::db::DatabaseManager;
+
+pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/scores",
+            post(handlers::scores::post::submit::handler)
+                .layer(from_fn_with_state(Scope::Import, require_scope)),
+        )
+        .route(
+            "/beatmaps/{beatmap_osu_id}/scores",
+            get(handlers::scores::get::by_beatmap::handler),
+        )
+        .with_state(db)
+}
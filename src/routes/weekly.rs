@@ -0,0 +1,36 @@
+//! # Weekly Routes Module
+//!
+//! Ce module configure les routes des sélections hebdomadaires.
+
+use crate::handlers;
+use crate::middleware::auth::{Scope, require_scope};
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{get, post},
+};
+use db::db::DatabaseManager;
+
+pub fn router(db: DatabaseManager) -> Router<DatabaseManager> {
+    Router::new()
+        .route(
+            "/weekly",
+            get(handlers::weekly::get::list::handler).merge(
+                post(handlers::weekly::post::create::handler)
+                    .layer(from_fn_with_state(Scope::Admin, require_scope)),
+            ),
+        )
+        .route(
+            "/weekly/current",
+            get(handlers::weekly::get::current::handler),
+        )
+        .route(
+            "/weekly/{id}",
+            get(handlers::weekly::get::find_one::handler),
+        )
+        .route(
+            "/weekly/{id}/leaderboard",
+            get(handlers::weekly::get::leaderboard::handler),
+        )
+        .with_state(db)
+}